@@ -0,0 +1,345 @@
+//! Cortex-M System Control Space (SCS) register map and decoding helpers.
+//!
+//! These registers are architecturally defined (ARMv6-M / ARMv7-M / ARMv8-M), so the
+//! addresses are the same on every Cortex-M part regardless of vendor. The helpers here
+//! are pure functions over raw register values; the memory access itself lives in
+//! `lib.rs`.
+
+/// CPUID Base Register: implementer, variant, part number and revision.
+pub const CPUID: u64 = 0xE000_ED00;
+
+/// Interrupt Control and State Register. VECTACTIVE (bits 8:0) mirrors IPSR.
+pub const ICSR: u64 = 0xE000_ED04;
+
+/// Vector Table Offset Register (not implemented on Cortex-M0).
+pub const VTOR: u64 = 0xE000_ED08;
+
+/// Application Interrupt and Reset Control Register. SCR, CCR and SHPR1-3 follow
+/// at +4, +8 and +12..+20.
+pub const AIRCR: u64 = 0xE000_ED0C;
+
+/// Debug Halting Control and Status Register.
+pub const DHCSR: u64 = 0xE000_EDF0;
+
+/// DHCSR.S_LOCKUP: the core is locked up after an unrecoverable fault.
+pub const DHCSR_S_LOCKUP: u32 = 1 << 19;
+
+/// Interrupt Controller Type Register. INTLINESNUM (bits 3:0) gives the number
+/// of implemented NVIC register words minus one. Not present on ARMv6-M, where it
+/// reads as zero (those cores have at most 32 IRQs).
+pub const ICTR: u64 = 0xE000_E004;
+
+/// SysTick Control and Status Register; RVR, CVR and CALIB follow at +4, +8, +12.
+pub const SYST_CSR: u64 = 0xE000_E010;
+
+/// Debug Exception and Monitor Control Register. TRCENA (bit 24) powers DWT/ITM.
+pub const DEMCR: u64 = 0xE000_EDFC;
+
+/// DEMCR.TRCENA: global enable for the DWT and ITM units.
+pub const DEMCR_TRCENA: u32 = 1 << 24;
+
+/// DWT Control Register.
+pub const DWT_CTRL: u64 = 0xE000_1000;
+
+/// DWT_CTRL.NOPRFCNT: the profiling counters (CPICNT..FOLDCNT) are not implemented.
+pub const DWT_CTRL_NOPRFCNT: u32 = 1 << 24;
+
+/// DWT_CTRL enable bits for CPICNT, EXCCNT, SLEEPCNT, LSUCNT and FOLDCNT (17-21).
+pub const DWT_CTRL_PROFILING_ENA: u32 = 0b11111 << 17;
+
+/// DWT Cycle Count Register; CPICNT, EXCCNT, SLEEPCNT, LSUCNT, FOLDCNT follow.
+pub const DWT_CYCCNT: u64 = 0xE000_1004;
+
+/// Floating-Point Context Control Register. LSPACT (bit 0) means lazy state
+/// preservation is pending: a frame has space reserved for S0-S15/FPSCR, but the
+/// values are still in the FPU registers.
+pub const FPCCR: u64 = 0xE000_EF34;
+
+/// Floating-Point Context Address Register: where the pending lazy state goes (S0).
+pub const FPCAR: u64 = 0xE000_EF38;
+
+/// MPU Type Register. DREGION (bits 15:8) is the number of regions; 0 = no MPU.
+pub const MPU_TYPE: u64 = 0xE000_ED90;
+
+/// MPU Control Register; MPU_RNR, MPU_RBAR and MPU_RASR/MPU_RLAR follow at +4, +8, +12.
+pub const MPU_CTRL: u64 = 0xE000_ED94;
+
+/// MPU Region Number Register: selects the region RBAR/RASR (RLAR) refer to.
+pub const MPU_RNR: u64 = 0xE000_ED98;
+
+/// NVIC Interrupt Set-Enable Registers (one bit per IRQ, 32 IRQs per word).
+pub const NVIC_ISER: u64 = 0xE000_E100;
+
+/// NVIC Interrupt Set-Pending Registers.
+pub const NVIC_ISPR: u64 = 0xE000_E200;
+
+/// NVIC Interrupt Active Bit Registers.
+pub const NVIC_IABR: u64 = 0xE000_E300;
+
+/// Name of the exception with the given exception number (as found in IPSR).
+///
+/// Numbers 16 and up are external interrupts and are reported as `IRQn`, where
+/// `n` is the exception number minus 16.
+pub fn exception_name(number: u32) -> String {
+    match number {
+        0 => "Thread".to_string(),
+        1 => "Reset".to_string(),
+        2 => "NMI".to_string(),
+        3 => "HardFault".to_string(),
+        4 => "MemManage".to_string(),
+        5 => "BusFault".to_string(),
+        6 => "UsageFault".to_string(),
+        7 => "SecureFault".to_string(),
+        11 => "SVCall".to_string(),
+        12 => "DebugMonitor".to_string(),
+        14 => "PendSV".to_string(),
+        15 => "SysTick".to_string(),
+        n if n >= 16 => format!("IRQ{}", n - 16),
+        n => format!("Reserved{}", n),
+    }
+}
+
+/// Number of interrupt lines the NVIC implements, from a raw ICTR value.
+///
+/// This is an upper bound in steps of 32; the part may wire up fewer IRQs.
+pub fn ictr_irq_lines(ictr: u32) -> usize {
+    ((ictr & 0xF) as usize + 1) * 32
+}
+
+/// Exception numbers with a configurable priority in SHPR1-3 (MemManage through
+/// SysTick; 8-10 and 13 are reserved).
+pub const SHPR_EXCEPTIONS: [u32; 8] = [4, 5, 6, 7, 11, 12, 14, 15];
+
+/// Priority byte of system exception `number` from the SHPR1-3 words. Each word
+/// holds four byte-wide fields, starting with exception 4 in SHPR1 bits 7:0.
+pub fn system_handler_priority(shpr: &[u32], number: u32) -> u8 {
+    let index = (number - 4) as usize;
+    (shpr[index / 4] >> (8 * (index % 4))) as u8
+}
+
+/// Expand NVIC bitmap words into the list of IRQ numbers whose bit is set.
+pub fn irq_bits(words: &[u32]) -> Vec<u32> {
+    let mut irqs = Vec::new();
+    for (word_index, word) in words.iter().enumerate() {
+        for bit in 0..32 {
+            if word & (1 << bit) != 0 {
+                irqs.push(word_index as u32 * 32 + bit);
+            }
+        }
+    }
+    irqs
+}
+
+/// Decoded fields of the CPUID register.
+pub struct Cpuid {
+    pub implementer: u8,
+    pub variant: u8,
+    pub architecture: u8,
+    pub part_number: u16,
+    pub revision: u8,
+}
+
+impl Cpuid {
+    pub fn decode(raw: u32) -> Cpuid {
+        Cpuid {
+            implementer: (raw >> 24) as u8,
+            variant: ((raw >> 20) & 0xF) as u8,
+            architecture: ((raw >> 16) & 0xF) as u8,
+            part_number: ((raw >> 4) & 0xFFF) as u16,
+            revision: (raw & 0xF) as u8,
+        }
+    }
+
+    /// Core name for ARM-implemented parts, e.g. "Cortex-M4".
+    pub fn core_name(&self) -> Option<&'static str> {
+        if self.implementer != 0x41 {
+            return None;
+        }
+        Some(match self.part_number {
+            0xC20 => "Cortex-M0",
+            0xC60 => "Cortex-M0+",
+            0xC21 => "Cortex-M1",
+            0xC23 => "Cortex-M3",
+            0xC24 => "Cortex-M4",
+            0xC27 => "Cortex-M7",
+            0xD20 => "Cortex-M23",
+            0xD21 => "Cortex-M33",
+            0xD22 => "Cortex-M55",
+            0xD23 => "Cortex-M85",
+            0xD31 => "Cortex-M35P",
+            _ => return None,
+        })
+    }
+
+    /// Revision in ARM's rNpM notation (variant = N, revision = M).
+    pub fn revision_string(&self) -> String {
+        format!("r{}p{}", self.variant, self.revision)
+    }
+
+    /// True for ARMv8-M cores, whose MPU uses base/limit (RBAR/RLAR) regions
+    /// instead of the ARMv7-M power-of-two RBAR/RASR regions.
+    pub fn is_armv8m(&self) -> bool {
+        self.implementer == 0x41 && matches!(self.part_number, 0xD20..=0xD23 | 0xD31)
+    }
+}
+
+/// Names of the words in a basic hardware-stacked exception frame, in stack order.
+pub const BASIC_FRAME: [&str; 8] = ["r0", "r1", "r2", "r3", "r12", "lr", "pc", "xpsr"];
+
+/// Words in an extended (FPU) frame: basic frame + S0-S15 + FPSCR + reserved.
+pub const EXTENDED_FRAME_WORDS: usize = 26;
+
+/// True if an EXC_RETURN value says the stacked frame includes FPU state
+/// (bit 4, FType, is 0 for an extended frame).
+pub fn exc_return_has_fpu_frame(exc_return: u32) -> bool {
+    exc_return & (1 << 4) == 0
+}
+
+/// xPSR bit 9: the core inserted a padding word to 8-byte align the frame.
+pub fn xpsr_stack_aligned(xpsr: u32) -> bool {
+    xpsr & (1 << 9) != 0
+}
+
+/// An enabled MPU region, decoded from either MPU flavour.
+pub struct MpuRegion {
+    pub base: u32,
+    /// Last address covered by the region (inclusive).
+    pub limit: u32,
+    /// Access for privileged code: "rw", "ro" or "none".
+    pub privileged: &'static str,
+    /// Access for unprivileged code: "rw", "ro" or "none".
+    pub unprivileged: &'static str,
+    pub executable: bool,
+    /// ARMv7-M only: bit N set disables the Nth eighth of the region.
+    pub subregion_disable: Option<u8>,
+    /// ARMv7-M TEX/C/B/S bits, or the ARMv8-M MAIR attribute index.
+    pub attributes: String,
+}
+
+/// Decode an ARMv7-M (and ARMv6-M) region from RBAR/RASR. None if disabled.
+pub fn decode_mpu_region_v7(rbar: u32, rasr: u32) -> Option<MpuRegion> {
+    if rasr & 1 == 0 {
+        return None;
+    }
+    // SIZE encodes 2^(SIZE+1) bytes; 31 covers the whole 4 GiB space
+    let size_bits = ((rasr >> 1) & 0x1F) + 1;
+    let base = rbar & !0x1F;
+    let limit = if size_bits >= 32 {
+        u32::MAX
+    } else {
+        base.wrapping_add((1u32 << size_bits) - 1)
+    };
+    let (privileged, unprivileged) = match (rasr >> 24) & 0b111 {
+        0b001 => ("rw", "none"),
+        0b010 => ("rw", "ro"),
+        0b011 => ("rw", "rw"),
+        0b101 => ("ro", "none"),
+        0b110 | 0b111 => ("ro", "ro"),
+        _ => ("none", "none"),
+    };
+    Some(MpuRegion {
+        base,
+        limit,
+        privileged,
+        unprivileged,
+        executable: rasr & (1 << 28) == 0,
+        subregion_disable: Some(((rasr >> 8) & 0xFF) as u8),
+        attributes: format!(
+            "TEX={} C={} B={} S={}",
+            (rasr >> 19) & 0b111,
+            (rasr >> 17) & 1,
+            (rasr >> 16) & 1,
+            (rasr >> 18) & 1
+        ),
+    })
+}
+
+/// Decode an ARMv8-M region from RBAR/RLAR. None if disabled.
+pub fn decode_mpu_region_v8(rbar: u32, rlar: u32) -> Option<MpuRegion> {
+    if rlar & 1 == 0 {
+        return None;
+    }
+    let (privileged, unprivileged) = match (rbar >> 1) & 0b11 {
+        0b00 => ("rw", "none"),
+        0b01 => ("rw", "rw"),
+        0b10 => ("ro", "none"),
+        _ => ("ro", "ro"),
+    };
+    Some(MpuRegion {
+        base: rbar & !0x1F,
+        limit: rlar | 0x1F,
+        privileged,
+        unprivileged,
+        executable: rbar & 1 == 0,
+        subregion_disable: None,
+        attributes: format!("AttrIndx={} SH={}", (rlar >> 1) & 0b111, (rbar >> 3) & 0b11),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn system_handler_priorities() {
+        let shpr = [0x4030_2010, 0x8000_0000, 0xF0E0_0000];
+        let cases = [(4, 0x10), (5, 0x20), (6, 0x30), (7, 0x40), (11, 0x80), (12, 0x00), (14, 0xE0), (15, 0xF0)];
+        for (number, priority) in cases {
+            assert_eq!(system_handler_priority(&shpr, number), priority, "exception {}", number);
+        }
+    }
+
+    #[test]
+    fn mpu_region_v7() {
+        // 128 KiB at 0x20000000: SIZE=16, full access, XN, TEX=1 C=1 B=0 S=1, SRD=0xA5
+        let rasr = 1 | 16 << 1 | 0xA5 << 8 | 1 << 17 | 1 << 18 | 1 << 19 | 0b011 << 24 | 1 << 28;
+        let region = decode_mpu_region_v7(0x2000_0000 | 0x10 | 3, rasr).unwrap();
+        assert_eq!((region.base, region.limit), (0x2000_0000, 0x2001_FFFF));
+        assert_eq!((region.privileged, region.unprivileged), ("rw", "rw"));
+        assert!(!region.executable);
+        assert_eq!(region.subregion_disable, Some(0xA5));
+        assert_eq!(region.attributes, "TEX=1 C=1 B=0 S=1");
+    }
+
+    #[test]
+    fn mpu_region_v7_access_and_size() {
+        let cases = [
+            (0b000, ("none", "none")),
+            (0b001, ("rw", "none")),
+            (0b010, ("rw", "ro")),
+            (0b101, ("ro", "none")),
+            (0b110, ("ro", "ro")),
+            (0b111, ("ro", "ro")),
+        ];
+        for (ap, access) in cases {
+            let region = decode_mpu_region_v7(0, 1 | 4 << 1 | ap << 24).unwrap();
+            assert_eq!((region.privileged, region.unprivileged), access, "AP={:03b}", ap);
+            assert_eq!(region.limit, 0x1F);
+            assert!(region.executable);
+        }
+
+        // SIZE=31 covers the whole address space without overflowing
+        let region = decode_mpu_region_v7(0, 1 | 31 << 1).unwrap();
+        assert_eq!(region.limit, u32::MAX);
+        assert!(decode_mpu_region_v7(0x2000_0000, 16 << 1).is_none());
+    }
+
+    #[test]
+    fn mpu_region_v8() {
+        // Flash 0x08000000-0x0807FFFF, read-only for all, SH=2, AttrIndx=2
+        let region = decode_mpu_region_v8(0x0800_0000 | 0b10 << 3 | 0b11 << 1, 0x0807_FFE0 | 2 << 1 | 1).unwrap();
+        assert_eq!((region.base, region.limit), (0x0800_0000, 0x0807_FFFF));
+        assert_eq!((region.privileged, region.unprivileged), ("ro", "ro"));
+        assert!(region.executable);
+        assert_eq!(region.subregion_disable, None);
+        assert_eq!(region.attributes, "AttrIndx=2 SH=2");
+
+        let cases = [(0b00, ("rw", "none")), (0b01, ("rw", "rw")), (0b10, ("ro", "none"))];
+        for (ap, access) in cases {
+            let region = decode_mpu_region_v8(ap << 1 | 1, 1).unwrap();
+            assert_eq!((region.privileged, region.unprivileged), access, "AP={:02b}", ap);
+            assert!(!region.executable);
+        }
+        assert!(decode_mpu_region_v8(0x0800_0000, 0x0807_FFE0).is_none());
+    }
+}
@@ -0,0 +1,659 @@
+//! Vendor-specific register maps, keyed by chip family.
+//!
+//! probe-rs knows how to talk to a chip, but not where each vendor keeps things like
+//! option bytes or reset-reason flags. This module maps a probe-rs chip name (e.g.
+//! "STM32L476RG") onto the series it belongs to and holds the per-series register
+//! addresses and decoders used by the family-aware methods in `lib.rs`.
+
+/// Chip series with a known register map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Family {
+    Stm32F0,
+    Stm32F1,
+    Stm32F2,
+    Stm32F3,
+    Stm32F4,
+    Stm32F7,
+    Stm32G0,
+    Stm32G4,
+    Stm32H7,
+    Stm32L4,
+    Stm32Wb,
+    Stm32Wl,
+    Nrf52,
+    Nrf53,
+    Nrf91,
+    Esp32C3,
+    Esp32C6,
+    Esp32S3,
+}
+
+impl Family {
+    /// Map a probe-rs chip name onto its series. Matching is case-insensitive and
+    /// based on the name prefix, so "STM32L476RG" and "stm32l4r5zi" both map to
+    /// `Stm32L4`.
+    pub fn from_chip(chip: &str) -> Option<Family> {
+        let chip = chip.to_ascii_lowercase();
+        let prefixes = [
+            ("stm32f0", Family::Stm32F0),
+            ("stm32f1", Family::Stm32F1),
+            ("stm32f2", Family::Stm32F2),
+            ("stm32f3", Family::Stm32F3),
+            ("stm32f4", Family::Stm32F4),
+            ("stm32f7", Family::Stm32F7),
+            ("stm32g0", Family::Stm32G0),
+            ("stm32g4", Family::Stm32G4),
+            ("stm32h7", Family::Stm32H7),
+            ("stm32l4", Family::Stm32L4),
+            ("stm32wb", Family::Stm32Wb),
+            ("stm32wl", Family::Stm32Wl),
+            ("nrf52", Family::Nrf52),
+            ("nrf53", Family::Nrf53),
+            ("nrf91", Family::Nrf91),
+            ("esp32c3", Family::Esp32C3),
+            ("esp32c6", Family::Esp32C6),
+            ("esp32s3", Family::Esp32S3),
+        ];
+        prefixes
+            .iter()
+            .find(|(prefix, _)| chip.starts_with(prefix))
+            .map(|&(_, family)| family)
+    }
+
+    /// Short human-readable series name, e.g. "STM32L4".
+    pub fn name(self) -> &'static str {
+        match self {
+            Family::Stm32F0 => "STM32F0",
+            Family::Stm32F1 => "STM32F1",
+            Family::Stm32F2 => "STM32F2",
+            Family::Stm32F3 => "STM32F3",
+            Family::Stm32F4 => "STM32F4",
+            Family::Stm32F7 => "STM32F7",
+            Family::Stm32G0 => "STM32G0",
+            Family::Stm32G4 => "STM32G4",
+            Family::Stm32H7 => "STM32H7",
+            Family::Stm32L4 => "STM32L4",
+            Family::Stm32Wb => "STM32WB",
+            Family::Stm32Wl => "STM32WL",
+            Family::Nrf52 => "nRF52",
+            Family::Nrf53 => "nRF53",
+            Family::Nrf91 => "nRF91",
+            Family::Esp32C3 => "ESP32-C3",
+            Family::Esp32C6 => "ESP32-C6",
+            Family::Esp32S3 => "ESP32-S3",
+        }
+    }
+}
+
+/// Where a family keeps its readout-protection setting.
+pub struct ProtectionRegister {
+    pub name: &'static str,
+    pub address: u64,
+}
+
+/// Decoded readout-protection state.
+pub struct FlashProtection {
+    /// STM32 RDP level (0, 1 or 2). None for families without numbered levels.
+    pub level: Option<u8>,
+    /// True if debug readout of flash is blocked.
+    pub readout_protected: bool,
+}
+
+/// Readout-protection register for `family`, or None if not supported.
+pub fn protection_register(family: Family) -> Option<ProtectionRegister> {
+    let (name, address) = match family {
+        Family::Stm32F0 | Family::Stm32F1 | Family::Stm32F3 => ("FLASH_OBR", 0x4002_201C),
+        Family::Stm32F2 | Family::Stm32F4 | Family::Stm32F7 => ("FLASH_OPTCR", 0x4002_3C14),
+        Family::Stm32G0 | Family::Stm32G4 | Family::Stm32L4 => ("FLASH_OPTR", 0x4002_2020),
+        Family::Stm32Wb | Family::Stm32Wl => ("FLASH_OPTR", 0x5800_4020),
+        Family::Stm32H7 => ("FLASH_OPTSR_CUR", 0x5200_201C),
+        Family::Nrf52 => ("UICR.APPROTECT", 0x1000_1208),
+        Family::Nrf53 | Family::Nrf91 => ("UICR.APPROTECT", 0x00FF_8000),
+        Family::Esp32C3 | Family::Esp32C6 | Family::Esp32S3 => return None,
+    };
+    Some(ProtectionRegister { name, address })
+}
+
+/// Decode an STM32 RDP byte: 0xAA is level 0, 0xCC is level 2, anything else is level 1.
+fn rdp_level(rdp: u32) -> u8 {
+    match rdp & 0xFF {
+        0xAA => 0,
+        0xCC => 2,
+        _ => 1,
+    }
+}
+
+/// Decode the raw value of [`protection_register`] for `family`.
+pub fn decode_protection(family: Family, raw: u32) -> FlashProtection {
+    let level = match family {
+        // RDPRT in FLASH_OBR: F1 has a single bit, F0/F3 encode the level in bits 2:1
+        Family::Stm32F1 => Some(((raw >> 1) & 1) as u8),
+        Family::Stm32F0 | Family::Stm32F3 => Some(match (raw >> 1) & 0b11 {
+            0b00 => 0,
+            0b11 => 2,
+            _ => 1,
+        }),
+        Family::Stm32F2 | Family::Stm32F4 | Family::Stm32F7 | Family::Stm32H7 => {
+            Some(rdp_level(raw >> 8))
+        }
+        Family::Stm32G0 | Family::Stm32G4 | Family::Stm32L4 | Family::Stm32Wb | Family::Stm32Wl => {
+            Some(rdp_level(raw))
+        }
+        _ => None,
+    };
+
+    let readout_protected = match family {
+        // PALL is bits 7:0. Erased (0xFF) means disabled on older silicon; newer
+        // revisions need 0x5A
+        Family::Nrf52 => !matches!(raw & 0xFF, 0xFF | 0x5A),
+        Family::Nrf53 | Family::Nrf91 => raw != 0x50FA_50FA,
+        _ => level.map_or(false, |l| l > 0),
+    };
+
+    FlashProtection {
+        level,
+        readout_protected,
+    }
+}
+
+/// Where a family records why the last reset happened, and how it's encoded.
+pub struct ResetCauseRegister {
+    pub name: &'static str,
+    pub address: u64,
+    pub encoding: ResetEncoding,
+}
+
+/// Reset-cause encodings: one flag bit per reason, or an enumerated code field.
+pub enum ResetEncoding {
+    /// (bit, reason) pairs; every set bit is a reason.
+    Flags(&'static [(u32, &'static str)]),
+    /// Bits 5:0 hold a single reset code; (code, reason) pairs.
+    Code(&'static [(u32, &'static str)]),
+}
+
+const STM32_F_CSR: &[(u32, &str)] = &[
+    (31, "low_power"),
+    (30, "window_watchdog"),
+    (29, "independent_watchdog"),
+    (28, "software"),
+    (27, "power_on"),
+    (26, "pin"),
+    (25, "brownout"),
+];
+
+const STM32_F1_CSR: &[(u32, &str)] = &[
+    (31, "low_power"),
+    (30, "window_watchdog"),
+    (29, "independent_watchdog"),
+    (28, "software"),
+    (27, "power_on"),
+    (26, "pin"),
+];
+
+const STM32_F0_CSR: &[(u32, &str)] = &[
+    (31, "low_power"),
+    (30, "window_watchdog"),
+    (29, "independent_watchdog"),
+    (28, "software"),
+    (27, "power_on"),
+    (26, "pin"),
+    (25, "option_byte_load"),
+    (23, "v18_power_domain"),
+];
+
+const STM32_L4_CSR: &[(u32, &str)] = &[
+    (31, "low_power"),
+    (30, "window_watchdog"),
+    (29, "independent_watchdog"),
+    (28, "software"),
+    (27, "brownout"),
+    (26, "pin"),
+    (25, "option_byte_load"),
+    (24, "firewall"),
+];
+
+/// G0 and G4: the L4 layout without FWRSTF (bit 24 is reserved, no firewall).
+const STM32_G_CSR: &[(u32, &str)] = &[
+    (31, "low_power"),
+    (30, "window_watchdog"),
+    (29, "independent_watchdog"),
+    (28, "software"),
+    (27, "brownout"),
+    (26, "pin"),
+    (25, "option_byte_load"),
+];
+
+const STM32_H7_RSR: &[(u32, &str)] = &[
+    (30, "low_power"),
+    (28, "window_watchdog"),
+    (26, "independent_watchdog"),
+    (24, "software"),
+    (23, "power_on"),
+    (22, "pin"),
+    (21, "brownout"),
+    (20, "d2_domain"),
+    (19, "d1_domain"),
+    (17, "cpu"),
+];
+
+const NRF52_RESETREAS: &[(u32, &str)] = &[
+    (0, "pin"),
+    (1, "watchdog"),
+    (2, "software"),
+    (3, "lockup"),
+    (16, "wakeup_from_off"),
+    (17, "lpcomp"),
+    (18, "debug_interface"),
+    (19, "nfc"),
+];
+
+const NRF53_RESETREAS: &[(u32, &str)] = &[
+    (0, "pin"),
+    (1, "watchdog"),
+    (2, "ctrl_ap"),
+    (3, "software"),
+    (4, "lockup"),
+    (5, "wakeup_from_off"),
+    (6, "lpcomp"),
+    (7, "debug_interface"),
+    (8, "network_core_software"),
+    (9, "network_core_lockup"),
+    (10, "network_core_watchdog"),
+    (23, "network_core_force_off"),
+    (24, "nfc"),
+    (25, "watchdog1"),
+    (26, "vbus"),
+];
+
+const NRF91_RESETREAS: &[(u32, &str)] = &[
+    (0, "pin"),
+    (1, "watchdog"),
+    (2, "wakeup_from_off"),
+    (3, "debug_interface"),
+    (4, "software"),
+    (5, "lockup"),
+    (6, "ctrl_ap"),
+];
+
+const ESP32_RESET_CODES: &[(u32, &str)] = &[
+    (1, "power_on"),
+    (3, "software"),
+    (5, "deep_sleep"),
+    (7, "timer_group0_watchdog"),
+    (8, "timer_group1_watchdog"),
+    (9, "rtc_watchdog"),
+    (11, "intrusion"),
+    (12, "timer_group0_watchdog_cpu"),
+    (13, "software_cpu"),
+    (14, "rtc_watchdog_cpu"),
+    (15, "brownout"),
+    (16, "rtc_watchdog_rtc"),
+    (17, "timer_group1_watchdog_cpu"),
+    (18, "super_watchdog"),
+    (19, "clock_glitch"),
+    (20, "efuse_crc_error"),
+    (21, "usb_uart"),
+    (22, "usb_jtag"),
+    (23, "power_glitch"),
+];
+
+/// Reset-cause register for `family`, or None if not supported.
+pub fn reset_cause_register(family: Family) -> Option<ResetCauseRegister> {
+    let (name, address, encoding) = match family {
+        Family::Stm32F0 | Family::Stm32F3 => ("RCC_CSR", 0x4002_1024, ResetEncoding::Flags(STM32_F0_CSR)),
+        Family::Stm32F1 => ("RCC_CSR", 0x4002_1024, ResetEncoding::Flags(STM32_F1_CSR)),
+        Family::Stm32F2 | Family::Stm32F4 | Family::Stm32F7 => {
+            ("RCC_CSR", 0x4002_3874, ResetEncoding::Flags(STM32_F_CSR))
+        }
+        Family::Stm32G0 => ("RCC_CSR", 0x4002_1060, ResetEncoding::Flags(STM32_G_CSR)),
+        Family::Stm32G4 => ("RCC_CSR", 0x4002_1094, ResetEncoding::Flags(STM32_G_CSR)),
+        Family::Stm32L4 => ("RCC_CSR", 0x4002_1094, ResetEncoding::Flags(STM32_L4_CSR)),
+        Family::Stm32Wb | Family::Stm32Wl => ("RCC_CSR", 0x5800_0094, ResetEncoding::Flags(STM32_L4_CSR)),
+        Family::Stm32H7 => ("RCC_RSR", 0x5802_44D0, ResetEncoding::Flags(STM32_H7_RSR)),
+        Family::Nrf52 => ("POWER.RESETREAS", 0x4000_0400, ResetEncoding::Flags(NRF52_RESETREAS)),
+        Family::Nrf53 => ("RESET.RESETREAS", 0x5000_5400, ResetEncoding::Flags(NRF53_RESETREAS)),
+        Family::Nrf91 => ("POWER.RESETREAS", 0x5000_5400, ResetEncoding::Flags(NRF91_RESETREAS)),
+        Family::Esp32C3 | Family::Esp32S3 => {
+            ("RTC_CNTL_RESET_STATE", 0x6000_8038, ResetEncoding::Code(ESP32_RESET_CODES))
+        }
+        Family::Esp32C6 => return None,
+    };
+    Some(ResetCauseRegister {
+        name,
+        address,
+        encoding,
+    })
+}
+
+/// Decode a raw reset-cause register value into reason names.
+///
+/// For flag registers only the listed bits are considered, since the same register
+/// usually carries unrelated control bits (e.g. LSION in RCC_CSR). An unknown
+/// enumerated code is reported as "unknown_codeN" rather than dropped.
+pub fn decode_reset_cause(encoding: &ResetEncoding, raw: u32) -> Vec<String> {
+    match encoding {
+        ResetEncoding::Flags(flags) => flags
+            .iter()
+            .filter(|(bit, _)| raw & (1 << bit) != 0)
+            .map(|(_, reason)| reason.to_string())
+            .collect(),
+        ResetEncoding::Code(codes) => {
+            let code = raw & 0x3F;
+            let reason = match codes.iter().find(|(c, _)| *c == code) {
+                Some((_, reason)) => reason.to_string(),
+                None => format!("unknown_code{}", code),
+            };
+            vec![reason]
+        }
+    }
+}
+
+/// Register that keeps the debug unit clocked in low-power modes, and the bits to set.
+pub struct LowPowerDebugRegister {
+    pub name: &'static str,
+    pub address: u64,
+    pub bits: u32,
+}
+
+/// DBG_SLEEP | DBG_STOP | DBG_STANDBY
+const DBG_SLEEP_STOP_STANDBY: u32 = 0b111;
+
+/// DBG_STOP | DBG_STANDBY (parts where debug always survives Sleep)
+const DBG_STOP_STANDBY: u32 = 0b110;
+
+/// Low-power debug register for `family`, or None if the family has no such
+/// control (e.g. nRF, which keeps the debug domain powered while a debugger
+/// holds a power-up request).
+pub fn low_power_debug_register(family: Family) -> Option<LowPowerDebugRegister> {
+    let (name, address, bits) = match family {
+        Family::Stm32F0 | Family::Stm32G0 => ("DBGMCU_CR", 0x4001_5804, DBG_STOP_STANDBY),
+        Family::Stm32F1
+        | Family::Stm32F2
+        | Family::Stm32F3
+        | Family::Stm32F4
+        | Family::Stm32F7
+        | Family::Stm32G4
+        | Family::Stm32L4
+        | Family::Stm32Wb
+        | Family::Stm32Wl => ("DBGMCU_CR", 0xE004_2004, DBG_SLEEP_STOP_STANDBY),
+        Family::Stm32H7 => ("DBGMCU_CR", 0x5C00_1004, DBG_SLEEP_STOP_STANDBY),
+        _ => return None,
+    };
+    Some(LowPowerDebugRegister { name, address, bits })
+}
+
+/// How a write-protection register marks protected flash.
+pub enum WriteProtectionEncoding {
+    /// `count` nWRP bits starting at bit `shift`, one per unit; a cleared bit means
+    /// protected. On STM32F4, OPTCR.SPRMOD (bit 31) switches the same bits to PCROP,
+    /// where a set bit means protected (PCROP sectors can't be programmed either).
+    ActiveLow { shift: u32, count: u32 },
+    /// WRP area register holding a start page in bits 7:0 and an end page in bits
+    /// 23:16; the area is enabled when start <= end.
+    PageRange,
+}
+
+/// Where a family keeps its flash write protection.
+pub struct WriteProtection {
+    /// What one protection unit is: "sector", "page" or "page_group" (F0/F1/F3,
+    /// where each WRPR bit covers a density-dependent number of pages).
+    pub unit: &'static str,
+    /// (name, address) of each register; all use `encoding`.
+    pub registers: &'static [(&'static str, u64)],
+    pub encoding: WriteProtectionEncoding,
+}
+
+const STM32_F1_WRP: &[(&str, u64)] = &[("FLASH_WRPR", 0x4002_2020)];
+const STM32_F4_WRP: &[(&str, u64)] = &[("FLASH_OPTCR", 0x4002_3C14)];
+const STM32_H7_WRP: &[(&str, u64)] = &[("FLASH_WPSN_CUR1R", 0x5200_2038)];
+const STM32_L4_WRP: &[(&str, u64)] = &[("FLASH_WRP1AR", 0x4002_202C), ("FLASH_WRP1BR", 0x4002_2030)];
+const STM32_WB_WRP: &[(&str, u64)] = &[("FLASH_WRP1AR", 0x5800_402C), ("FLASH_WRP1BR", 0x5800_4030)];
+
+/// Write-protection registers for `family` (bank 1 only on dual-bank parts, whose
+/// bank 2 registers don't exist on the single-bank variants), or None if not supported.
+pub fn write_protection(family: Family) -> Option<WriteProtection> {
+    let (unit, registers, encoding) = match family {
+        Family::Stm32F0 | Family::Stm32F1 | Family::Stm32F3 => (
+            "page_group",
+            STM32_F1_WRP,
+            WriteProtectionEncoding::ActiveLow { shift: 0, count: 32 },
+        ),
+        Family::Stm32F2 | Family::Stm32F4 => (
+            "sector",
+            STM32_F4_WRP,
+            WriteProtectionEncoding::ActiveLow { shift: 16, count: 12 },
+        ),
+        Family::Stm32F7 => (
+            "sector",
+            STM32_F4_WRP,
+            WriteProtectionEncoding::ActiveLow { shift: 16, count: 8 },
+        ),
+        Family::Stm32H7 => (
+            "sector",
+            STM32_H7_WRP,
+            WriteProtectionEncoding::ActiveLow { shift: 0, count: 8 },
+        ),
+        Family::Stm32G0 | Family::Stm32G4 | Family::Stm32L4 => (
+            "page",
+            STM32_L4_WRP,
+            WriteProtectionEncoding::PageRange,
+        ),
+        Family::Stm32Wb | Family::Stm32Wl => (
+            "page",
+            STM32_WB_WRP,
+            WriteProtectionEncoding::PageRange,
+        ),
+        _ => return None,
+    };
+    Some(WriteProtection {
+        unit,
+        registers,
+        encoding,
+    })
+}
+
+/// Decode one raw write-protection register into the protected unit numbers.
+pub fn decode_write_protection(family: Family, encoding: &WriteProtectionEncoding, raw: u32) -> Vec<u32> {
+    match *encoding {
+        WriteProtectionEncoding::ActiveLow { shift, count } => {
+            let pcrop = family == Family::Stm32F4 && raw & (1 << 31) != 0;
+            (0..count)
+                .filter(|unit| (raw >> (shift + unit)) & 1 == u32::from(pcrop))
+                .collect()
+        }
+        WriteProtectionEncoding::PageRange => {
+            let (start, end) = (raw & 0xFF, (raw >> 16) & 0xFF);
+            if start <= end {
+                (start..=end).collect()
+            } else {
+                Vec::new()
+            }
+        }
+    }
+}
+
+/// RTC_TR, RTC_DR and RTC_ISR/RTC_ICSR offsets and bits (same on every STM32
+/// with the calendar RTC).
+pub const RTC_TR: u64 = 0x00;
+pub const RTC_DR: u64 = 0x04;
+pub const RTC_ISR: u64 = 0x0C;
+pub const RTC_WPR: u64 = 0x24;
+
+/// ISR.INIT requests initialization mode; ISR.INITF confirms it.
+pub const RTC_ISR_INIT: u32 = 1 << 7;
+pub const RTC_ISR_INITF: u32 = 1 << 6;
+
+/// Key sequence written to RTC_WPR to unlock the RTC registers; any other value
+/// locks them again.
+pub const RTC_WPR_UNLOCK: [u32; 2] = [0xCA, 0x53];
+pub const RTC_WPR_LOCK: u32 = 0xFF;
+
+/// Base address of the calendar RTC for `family`, or None if the family has none.
+///
+/// STM32F1 only has a 32-bit seconds counter and nRF RTCs are plain tick counters,
+/// so there is no calendar to convert to or from a date.
+pub fn rtc_base(family: Family) -> Option<u64> {
+    match family {
+        Family::Stm32F0
+        | Family::Stm32F2
+        | Family::Stm32F3
+        | Family::Stm32F4
+        | Family::Stm32F7
+        | Family::Stm32G0
+        | Family::Stm32G4
+        | Family::Stm32L4
+        | Family::Stm32Wb
+        | Family::Stm32Wl => Some(0x4000_2800),
+        Family::Stm32H7 => Some(0x5800_4000),
+        _ => None,
+    }
+}
+
+/// Calendar date and time as held by the STM32 RTC (24-hour format).
+pub struct RtcDateTime {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    /// ISO weekday, 1 = Monday ... 7 = Sunday (matches RTC_DR.WDU)
+    pub weekday: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+fn from_bcd(tens: u32, units: u32) -> u8 {
+    (tens * 10 + units) as u8
+}
+
+fn to_bcd(value: u8) -> u32 {
+    u32::from(value / 10) << 4 | u32::from(value % 10)
+}
+
+impl RtcDateTime {
+    /// Decode RTC_TR and RTC_DR. Years are 2000-2099.
+    pub fn decode(tr: u32, dr: u32) -> RtcDateTime {
+        RtcDateTime {
+            year: 2000 + u16::from(from_bcd((dr >> 20) & 0xF, (dr >> 16) & 0xF)),
+            month: from_bcd((dr >> 12) & 0x1, (dr >> 8) & 0xF),
+            day: from_bcd((dr >> 4) & 0x3, dr & 0xF),
+            weekday: ((dr >> 13) & 0x7) as u8,
+            hour: from_bcd((tr >> 20) & 0x3, (tr >> 16) & 0xF),
+            minute: from_bcd((tr >> 12) & 0x7, (tr >> 8) & 0xF),
+            second: from_bcd((tr >> 4) & 0x7, tr & 0xF),
+        }
+    }
+
+    /// Encode into (RTC_TR, RTC_DR) values. `year` must be 2000-2099.
+    pub fn encode(&self) -> (u32, u32) {
+        let tr = to_bcd(self.hour) << 16 | to_bcd(self.minute) << 8 | to_bcd(self.second);
+        let dr = to_bcd((self.year - 2000) as u8) << 16
+            | u32::from(self.weekday) << 13
+            | to_bcd(self.month) << 8
+            | to_bcd(self.day);
+        (tr, dr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flags(family: Family) -> &'static [(u32, &'static str)] {
+        match reset_cause_register(family).map(|register| register.encoding) {
+            Some(ResetEncoding::Flags(flags)) => flags,
+            _ => panic!("{:?} has no flag-encoded reset cause register", family),
+        }
+    }
+
+    #[test]
+    fn reset_cause_registers() {
+        let cases = [
+            (Family::Stm32F0, "RCC_CSR", 0x4002_1024),
+            (Family::Stm32F1, "RCC_CSR", 0x4002_1024),
+            (Family::Stm32F4, "RCC_CSR", 0x4002_3874),
+            (Family::Stm32G0, "RCC_CSR", 0x4002_1060),
+            (Family::Stm32G4, "RCC_CSR", 0x4002_1094),
+            (Family::Stm32L4, "RCC_CSR", 0x4002_1094),
+            (Family::Stm32Wb, "RCC_CSR", 0x5800_0094),
+            (Family::Stm32H7, "RCC_RSR", 0x5802_44D0),
+            (Family::Nrf52, "POWER.RESETREAS", 0x4000_0400),
+            (Family::Nrf53, "RESET.RESETREAS", 0x5000_5400),
+            (Family::Esp32C3, "RTC_CNTL_RESET_STATE", 0x6000_8038),
+        ];
+        for (family, name, address) in cases {
+            let register = reset_cause_register(family).unwrap();
+            assert_eq!((register.name, register.address), (name, address), "{:?}", family);
+        }
+        assert!(reset_cause_register(Family::Esp32C6).is_none());
+    }
+
+    #[test]
+    fn firewall_flag_only_where_implemented() {
+        let cases = [
+            (Family::Stm32G0, false),
+            (Family::Stm32G4, false),
+            (Family::Stm32L4, true),
+            (Family::Stm32Wb, true),
+            (Family::Stm32Wl, true),
+        ];
+        for (family, has_firewall) in cases {
+            let decoded = decode_reset_cause(&ResetEncoding::Flags(flags(family)), 1 << 24);
+            assert_eq!(decoded == ["firewall"], has_firewall, "{:?}", family);
+        }
+    }
+
+    #[test]
+    fn decode_flag_registers() {
+        let cases: [(Family, u32, &[&str]); 6] = [
+            // LSION (bit 0) and RMVF (bit 24) are control bits, not reasons
+            (Family::Stm32F4, 0x0100_0001 | 1 << 26 | 1 << 29, &["independent_watchdog", "pin"]),
+            (Family::Stm32F1, 1 << 25, &[]),
+            (Family::Stm32F0, 1 << 25 | 1 << 23, &["option_byte_load", "v18_power_domain"]),
+            (Family::Stm32G4, 1 << 27 | 1 << 26, &["brownout", "pin"]),
+            (Family::Stm32H7, 1 << 22 | 1 << 21, &["pin", "brownout"]),
+            (Family::Nrf52, 1 << 2 | 1 << 18, &["software", "debug_interface"]),
+        ];
+        for (family, raw, expected) in cases {
+            assert_eq!(decode_reset_cause(&ResetEncoding::Flags(flags(family)), raw), expected, "{:?}", family);
+        }
+    }
+
+    #[test]
+    fn rtc_date_time_round_trip() {
+        let time = RtcDateTime {
+            year: 2099,
+            month: 12,
+            day: 31,
+            weekday: 4,
+            hour: 23,
+            minute: 59,
+            second: 59,
+        };
+        let (tr, dr) = time.encode();
+        assert_eq!(tr, 0x0023_5959);
+        assert_eq!(dr, 0x0099_9231);
+
+        let decoded = RtcDateTime::decode(tr, dr);
+        assert_eq!(
+            (decoded.year, decoded.month, decoded.day, decoded.weekday),
+            (2099, 12, 31, 4)
+        );
+        assert_eq!((decoded.hour, decoded.minute, decoded.second), (23, 59, 59));
+    }
+
+    #[test]
+    fn rtc_decode_ignores_control_bits() {
+        // RTC_TR.PM (bit 22) and reserved bits above the date fields are not part of the value
+        let decoded = RtcDateTime::decode(1 << 22 | 0x0001_0203, 0xFF00_0000 | 0x0000_2101);
+        assert_eq!((decoded.year, decoded.month, decoded.day, decoded.weekday), (2000, 1, 1, 1));
+        assert_eq!((decoded.hour, decoded.minute, decoded.second), (1, 2, 3));
+    }
+
+    #[test]
+    fn decode_code_register() {
+        let encoding = ResetEncoding::Code(ESP32_RESET_CODES);
+        // Only bits 5:0 hold the code
+        assert_eq!(decode_reset_cause(&encoding, 0x40 | 15), ["brownout"]);
+        assert_eq!(decode_reset_cause(&encoding, 2), ["unknown_code2"]);
+    }
+}
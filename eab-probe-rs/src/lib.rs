@@ -41,47 +41,77 @@
 //! session.detach()
 //! ```
 
+mod cortex_m;
+mod family;
+
 use probe_rs::{
-    probe::list::Lister,
+    probe::{list::Lister, DebugProbeInfo, WireProtocol},
     rtt::Rtt,
-    Permissions, Session,
+    config::{MemoryRegion, ScanChainElement},
+    Architecture, Core, CoreStatus, MemoryInterface, Permissions, Session,
 };
+use family::Family;
+use pyo3::exceptions::PyBufferError;
+use pyo3::ffi;
 use pyo3::prelude::*;
-use pyo3::types::PyBytes;
-use std::sync::Mutex;
+use pyo3::types::{PyBytes, PyDateAccess, PyDateTime, PyDict, PyList, PyTimeAccess};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use std::collections::{HashMap, VecDeque};
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
 use std::fs;
-use object::{Object, ObjectSymbol};
+use std::io::{BufWriter, Write};
+use std::ptr;
+use object::{Object, ObjectSection, ObjectSymbol, SectionKind};
 
-/// Parse an ELF file and extract the RTT control block address from the _SEGGER_RTT symbol.
-///
-/// # Arguments
-/// * `elf_path` - Path to the ELF file (e.g., "build/zephyr/zephyr.elf")
-///
-/// # Returns
-/// * `Ok(Some(address))` - Symbol found at this address
-/// * `Ok(None)` - ELF parsed successfully but no _SEGGER_RTT symbol found
-/// * `Err(...)` - Failed to read or parse the ELF file
-fn find_rtt_symbol(elf_path: &str) -> PyResult<Option<u64>> {
-    // Read the ELF file
-    let file_data = fs::read(elf_path).map_err(|e| {
+// Raised for RTT protocol-level failures (e.g. a payload that doesn't fit the down
+// buffer). Subclasses RuntimeError so existing `except RuntimeError` handlers still
+// catch it.
+pyo3::create_exception!(eab_probe_rs, RttError, pyo3::exceptions::PyRuntimeError);
+
+// Raised when the probe has gone away (USB unplugged) mid-session. Also a
+// RuntimeError subclass; the session stays unusable until the next attach().
+pyo3::create_exception!(eab_probe_rs, ProbeDisconnectedError, pyo3::exceptions::PyRuntimeError);
+
+/// Read an ELF file from disk, mapping I/O failures to IOError.
+fn read_elf(elf_path: &str) -> PyResult<Vec<u8>> {
+    fs::read(elf_path).map_err(|e| {
         pyo3::exceptions::PyIOError::new_err(format!(
             "Failed to read ELF file '{}': {}",
             elf_path, e
         ))
-    })?;
+    })
+}
 
-    // Parse the ELF
-    let elf_file = object::File::parse(&*file_data).map_err(|e| {
+/// Parse ELF bytes previously loaded with [`read_elf`], mapping failures to ValueError.
+fn parse_elf<'data>(elf_path: &str, file_data: &'data [u8]) -> PyResult<object::File<'data>> {
+    object::File::parse(file_data).map_err(|e| {
         pyo3::exceptions::PyValueError::new_err(format!(
             "Failed to parse ELF file '{}': {}",
             elf_path, e
         ))
-    })?;
+    })
+}
 
-    // Search for _SEGGER_RTT symbol
+/// Parse an ELF file and look up the address of a named symbol.
+///
+/// # Arguments
+/// * `elf_path` - Path to the ELF file (e.g., "build/zephyr/zephyr.elf")
+/// * `symbol_name` - Exact symbol name to look for (e.g., "_SEGGER_RTT")
+///
+/// # Returns
+/// * `Ok(Some(address))` - Symbol found at this address
+/// * `Ok(None)` - ELF parsed successfully but the symbol was not found
+/// * `Err(...)` - Failed to read or parse the ELF file
+fn find_symbol(elf_path: &str, symbol_name: &str) -> PyResult<Option<u64>> {
+    let file_data = read_elf(elf_path)?;
+    let elf_file = parse_elf(elf_path, &file_data)?;
+
+    // Search for the symbol
     for symbol in elf_file.symbols() {
         if let Ok(name) = symbol.name() {
-            if name == "_SEGGER_RTT" {
+            if name == symbol_name {
                 return Ok(Some(symbol.address()));
             }
         }
@@ -91,76 +121,5486 @@ fn find_rtt_symbol(elf_path: &str) -> PyResult<Option<u64>> {
     Ok(None)
 }
 
-/// A probe-rs session with RTT support.
+/// Look up several symbols in one pass over the ELF symbol table.
 ///
-/// This class wraps a probe-rs `Session` and provides methods for:
-/// - Attaching to a target chip via a debug probe
-/// - Starting RTT (Real-Time Transfer) for high-speed data streaming
-/// - Reading/writing raw bytes on RTT channels
-/// - Resetting the target
+/// Every name must be present; missing symbols are reported together in a single
+/// ValueError so the caller can fix all of them at once.
+fn find_symbols(elf_path: &str, symbol_names: &[&str]) -> PyResult<HashMap<String, u64>> {
+    let file_data = read_elf(elf_path)?;
+    let elf_file = parse_elf(elf_path, &file_data)?;
+
+    let mut found = HashMap::new();
+    for symbol in elf_file.symbols() {
+        if let Ok(name) = symbol.name() {
+            if symbol_names.contains(&name) && !found.contains_key(name) {
+                found.insert(name.to_string(), symbol.address());
+            }
+        }
+    }
+
+    let missing: Vec<&str> = symbol_names
+        .iter()
+        .copied()
+        .filter(|name| !found.contains_key(*name))
+        .collect();
+    if !missing.is_empty() {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Symbols not found in ELF file '{}': {}",
+            elf_path,
+            missing.join(", ")
+        )));
+    }
+
+    Ok(found)
+}
+
+/// Parse an ELF file and extract the RTT control block address from the _SEGGER_RTT symbol.
 ///
-/// # Thread Safety
+/// Thin wrapper around [`find_symbol`]; see it for the return value semantics.
+fn find_rtt_symbol(elf_path: &str) -> PyResult<Option<u64>> {
+    find_symbol(elf_path, "_SEGGER_RTT")
+}
+
+/// Look up a symbol that the caller requires to exist.
 ///
-/// The session is wrapped in a Mutex because probe-rs requires exclusive access
-/// to the debug probe. Python's GIL ensures only one thread accesses this at a time,
-/// but we use Mutex for Rust safety guarantees.
-#[pyclass]
-struct ProbeRsSession {
-    /// The active probe-rs session (probe + core state).
-    /// None if not connected.
-    session: Mutex<Option<Session>>,
+/// Same as [`find_symbol`], but a missing symbol is an error (ValueError) naming
+/// both the symbol and the ELF file, so callers don't have to handle `None`.
+fn require_symbol(elf_path: &str, symbol_name: &str) -> PyResult<u64> {
+    find_symbol(elf_path, symbol_name)?.ok_or_else(|| {
+        pyo3::exceptions::PyValueError::new_err(format!(
+            "Symbol '{}' not found in ELF file '{}'",
+            symbol_name, elf_path
+        ))
+    })
+}
 
-    /// RTT control block state.
-    /// None until start_rtt() is called.
-    rtt: Mutex<Option<Rtt>>,
+/// A loadable, read-only ELF section (code or constant data) and its contents.
+struct ImageSection {
+    name: String,
+    address: u64,
+    data: Vec<u8>,
+}
 
-    /// Target chip name (e.g., "STM32L476RG", "nRF52840_xxAA").
-    chip: String,
+/// Collect the ELF sections that end up in non-volatile memory (code and rodata).
+///
+/// Writable sections (`.data`, `.bss`) are skipped: their runtime contents differ
+/// from the ELF image, so they can't be compared against the device.
+fn image_sections(elf_path: &str) -> PyResult<Vec<ImageSection>> {
+    let file_data = read_elf(elf_path)?;
+    let elf_file = parse_elf(elf_path, &file_data)?;
 
-    /// Optional probe selector (serial number or VID:PID).
-    /// If None, uses the first available probe.
-    probe_selector: Option<String>,
+    let mut sections = Vec::new();
+    for section in elf_file.sections() {
+        if !matches!(section.kind(), SectionKind::Text | SectionKind::ReadOnlyData) {
+            continue;
+        }
+        let data = match section.data() {
+            Ok(data) if !data.is_empty() => data,
+            _ => continue,
+        };
+        sections.push(ImageSection {
+            name: section.name().unwrap_or("?").to_string(),
+            address: section.address(),
+            data: data.to_vec(),
+        });
+    }
+    Ok(sections)
 }
 
-#[pymethods]
-impl ProbeRsSession {
-    /// Create a new probe-rs session for the specified chip.
-    ///
-    /// Args:
-    ///     chip: Target chip name (e.g., "STM32L476RG", "nRF52840_xxAA")
-    ///     probe_selector: Optional probe selector string (serial, VID:PID, or index)
-    ///
-    /// Returns:
-    ///     ProbeRsSession instance (not yet connected — call attach() next)
-    ///
-    /// Example:
-    ///     >>> session = ProbeRsSession(chip="STM32L476RG")
-    ///     >>> session = ProbeRsSession(chip="nRF52840_xxAA", probe_selector="0483:374b")
-    #[new]
-    #[pyo3(signature = (chip, probe_selector=None))]
-    fn new(chip: String, probe_selector: Option<String>) -> Self {
-        Self {
-            session: Mutex::new(None),
-            rtt: Mutex::new(None),
-            chip,
-            probe_selector,
+/// True for probe errors that mean "try again", not "this access is invalid".
+///
+/// A WAIT response (the AP or target is still busy, e.g. while flash is being
+/// programmed or clocks are switching right after reset), a missing ACK while the
+/// debug port comes back up, or a timeout. A FAULT response is a genuine bus
+/// error and is never retried.
+fn is_transient(error: &probe_rs::Error) -> bool {
+    use probe_rs::architecture::arm::{ArmError, DapError};
+    use probe_rs::probe::DebugProbeError;
+
+    matches!(
+        error,
+        probe_rs::Error::Arm(
+            ArmError::Timeout | ArmError::Dap(DapError::WaitResponse | DapError::NoAcknowledge)
+        ) | probe_rs::Error::Probe(DebugProbeError::Timeout)
+    )
+}
+
+/// True for probe errors that mean the access itself was refused: a FAULT response
+/// from an unmapped, unclocked or reserved address. Retrying won't help, but the
+/// probe and the rest of the bus are fine.
+fn is_bus_fault(error: &probe_rs::Error) -> bool {
+    use probe_rs::architecture::arm::{ArmError, DapError};
+
+    matches!(
+        error,
+        probe_rs::Error::Arm(
+            ArmError::Dap(DapError::FaultResponse) | ArmError::AccessPort { .. } | ArmError::OutOfBounds
+        )
+    )
+}
+
+/// True for probe errors that mean the probe itself is gone (USB device unplugged
+/// or reset), as opposed to a failed access through a probe that is still there.
+fn is_disconnect(error: &probe_rs::Error) -> bool {
+    use probe_rs::architecture::arm::ArmError;
+    use probe_rs::probe::DebugProbeError;
+
+    matches!(
+        error,
+        probe_rs::Error::Probe(DebugProbeError::Usb(_))
+            | probe_rs::Error::Arm(ArmError::Probe(DebugProbeError::Usb(_)))
+    )
+}
+
+/// How many times `retry_transient` retries, and how long it pauses in between.
+#[derive(Clone, Copy)]
+struct RetryPolicy {
+    retries: usize,
+    delay: Duration,
+}
+
+/// Run a memory access, retrying a few times on transient errors.
+///
+/// Bounded by `policy`, the session's `retry_policy` (3 extra attempts, 2 ms apart
+/// by default), so a persistent failure still surfaces within a few milliseconds.
+fn retry_transient<T>(
+    policy: RetryPolicy,
+    mut access: impl FnMut() -> Result<T, probe_rs::Error>,
+) -> Result<T, probe_rs::Error> {
+    let mut attempt = 0;
+    loop {
+        match access() {
+            Err(e) if attempt < policy.retries && is_transient(&e) => {
+                attempt += 1;
+                std::thread::sleep(policy.delay);
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Read a single 32-bit register, naming the address in the error.
+fn read_reg(core: &mut Core, policy: RetryPolicy, address: u64) -> PyResult<u32> {
+    retry_transient(policy, || core.read_word_32(address)).map_err(|e| {
+        pyo3::exceptions::PyRuntimeError::new_err(format!(
+            "Failed to read register at 0x{:08x}: {}",
+            address, e
+        ))
+    })
+}
+
+/// Read `count` consecutive 32-bit registers starting at `address`.
+fn read_regs(core: &mut Core, policy: RetryPolicy, address: u64, count: usize) -> PyResult<Vec<u32>> {
+    let mut words = vec![0u32; count];
+    retry_transient(policy, || core.read_32(address, &mut words)).map_err(|e| {
+        pyo3::exceptions::PyRuntimeError::new_err(format!(
+            "Failed to read registers at 0x{:08x}: {}",
+            address, e
+        ))
+    })?;
+    Ok(words)
+}
+
+/// Read a NUL-terminated string of at most `max_len` bytes starting at `address`.
+///
+/// Reads in small chunks so a short string costs one transfer, and stops at the
+/// first NUL. The terminator is not included; if none is found within `max_len`
+/// the string is returned truncated.
+fn read_cstring(core: &mut Core, policy: RetryPolicy, address: u64, max_len: usize) -> PyResult<Vec<u8>> {
+    const CHUNK: usize = 64;
+
+    let mut bytes = Vec::new();
+    while bytes.len() < max_len {
+        let mut chunk = vec![0u8; CHUNK.min(max_len - bytes.len())];
+        let chunk_address = address + bytes.len() as u64;
+        retry_transient(policy, || core.read(chunk_address, &mut chunk)).map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!(
+                "Failed to read string at 0x{:08x}: {}",
+                chunk_address, e
+            ))
+        })?;
+        if let Some(nul) = chunk.iter().position(|&b| b == 0) {
+            bytes.extend_from_slice(&chunk[..nul]);
+            return Ok(bytes);
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(bytes)
+}
+
+/// Scalar C type of a global variable, as named by Python callers ("u32", "f32", ...).
+///
+/// Values are decoded little-endian, which covers Cortex-M, RISC-V and Xtensa targets.
+#[derive(Clone, Copy)]
+enum ValueType {
+    U8,
+    I8,
+    U16,
+    I16,
+    U32,
+    I32,
+    U64,
+    I64,
+    F32,
+    F64,
+    Bool,
+}
+
+impl ValueType {
+    fn parse(name: &str) -> PyResult<ValueType> {
+        Ok(match name {
+            "u8" => ValueType::U8,
+            "i8" => ValueType::I8,
+            "u16" => ValueType::U16,
+            "i16" => ValueType::I16,
+            "u32" => ValueType::U32,
+            "i32" => ValueType::I32,
+            "u64" => ValueType::U64,
+            "i64" => ValueType::I64,
+            "f32" => ValueType::F32,
+            "f64" => ValueType::F64,
+            "bool" => ValueType::Bool,
+            other => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Unknown type '{}'. Expected one of: u8, i8, u16, i16, u32, i32, u64, i64, f32, f64, bool",
+                    other
+                )))
+            }
+        })
+    }
+
+    /// Size of the type in target memory, in bytes.
+    fn size(self) -> usize {
+        match self {
+            ValueType::U8 | ValueType::I8 | ValueType::Bool => 1,
+            ValueType::U16 | ValueType::I16 => 2,
+            ValueType::U32 | ValueType::I32 | ValueType::F32 => 4,
+            ValueType::U64 | ValueType::I64 | ValueType::F64 => 8,
+        }
+    }
+
+    /// Fail with ValueError unless this is one of the integer types.
+    fn require_int(self) -> PyResult<()> {
+        if matches!(self, ValueType::F32 | ValueType::F64 | ValueType::Bool) {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "Expected an integer type: u8, i8, u16, i16, u32, i32, u64 or i64",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Little-endian bytes of integer `value` for this integer type, or ValueError
+    /// if it doesn't fit.
+    fn encode_int(self, value: i128) -> PyResult<Vec<u8>> {
+        self.require_int()?;
+        let (min, max) = match self {
+            ValueType::U8 => (0, u8::MAX as i128),
+            ValueType::I8 => (i8::MIN as i128, i8::MAX as i128),
+            ValueType::U16 => (0, u16::MAX as i128),
+            ValueType::I16 => (i16::MIN as i128, i16::MAX as i128),
+            ValueType::U32 => (0, u32::MAX as i128),
+            ValueType::I32 => (i32::MIN as i128, i32::MAX as i128),
+            ValueType::U64 => (0, u64::MAX as i128),
+            _ => (i64::MIN as i128, i64::MAX as i128),
+        };
+        if value < min || value > max {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "{} out of range for a {}-byte integer ({}..={})",
+                value,
+                self.size(),
+                min,
+                max
+            )));
+        }
+        Ok(value.to_le_bytes()[..self.size()].to_vec())
+    }
+
+    /// Convert `bytes` (exactly `size()` long) into the matching Python object.
+    fn decode<'py>(self, py: Python<'py>, bytes: &[u8]) -> PyResult<Bound<'py, PyAny>> {
+        let mut buf = [0u8; 8];
+        buf[..bytes.len()].copy_from_slice(bytes);
+        let raw = u64::from_le_bytes(buf);
+
+        Ok(match self {
+            ValueType::U8 => (raw as u8).into_pyobject(py)?.into_any(),
+            ValueType::I8 => (raw as u8 as i8).into_pyobject(py)?.into_any(),
+            ValueType::U16 => (raw as u16).into_pyobject(py)?.into_any(),
+            ValueType::I16 => (raw as u16 as i16).into_pyobject(py)?.into_any(),
+            ValueType::U32 => (raw as u32).into_pyobject(py)?.into_any(),
+            ValueType::I32 => (raw as u32 as i32).into_pyobject(py)?.into_any(),
+            ValueType::U64 => raw.into_pyobject(py)?.into_any(),
+            ValueType::I64 => (raw as i64).into_pyobject(py)?.into_any(),
+            ValueType::F32 => f32::from_bits(raw as u32).into_pyobject(py)?.into_any(),
+            ValueType::F64 => f64::from_bits(raw).into_pyobject(py)?.into_any(),
+            ValueType::Bool => (raw != 0).into_pyobject(py)?.to_owned().into_any(),
+        })
+    }
+}
+
+/// USB VID:PID of the USB-Serial-JTAG peripheral built into ESP32-C3/C6/S3.
+const ESP_USB_JTAG: (u16, u16) = (0x303a, 0x1001);
+
+/// Parse a "VID:PID" or "VID:PID:SERIAL" probe selector (VID/PID in hex).
+///
+/// Returns None if `selector` isn't in that form, in which case it is matched as a
+/// plain serial/identifier substring instead.
+fn parse_usb_selector(selector: &str) -> Option<(u16, u16, Option<String>)> {
+    let mut parts = selector.splitn(3, ':');
+    let vid = u16::from_str_radix(parts.next()?, 16).ok()?;
+    let pid = u16::from_str_radix(parts.next()?, 16).ok()?;
+    let serial = parts.next().map(str::to_string);
+    Some((vid, pid, serial))
+}
+
+/// Prefix of the Zephyr boot banner literal, which embeds the build version
+/// ("*** Booting Zephyr OS build v3.6.0 ***").
+const ZEPHYR_BANNER: &[u8] = b"Booting Zephyr OS build ";
+
+/// GNU build-id note from an ELF: the note contents and its load address
+/// (0 if the note section isn't allocated into the image).
+fn gnu_build_id(elf_path: &str) -> PyResult<Option<(u64, Vec<u8>)>> {
+    let file_data = read_elf(elf_path)?;
+    let elf_file = parse_elf(elf_path, &file_data)?;
+
+    let Some(section) = elf_file.section_by_name(".note.gnu.build-id") else {
+        return Ok(None);
+    };
+    let data = section.data().unwrap_or_default();
+
+    // Note layout: namesz, descsz, type (u32 each), name padded to 4 bytes, desc
+    if data.len() < 12 {
+        return Ok(None);
+    }
+    let word = |i: usize| u32::from_le_bytes([data[i], data[i + 1], data[i + 2], data[i + 3]]) as usize;
+    let (namesz, descsz) = (word(0), word(4));
+    let desc_start = 12 + namesz.next_multiple_of(4);
+    let Some(desc) = data.get(desc_start..desc_start + descsz) else {
+        return Ok(None);
+    };
+    Ok(Some((section.address() + desc_start as u64, desc.to_vec())))
+}
+
+/// Hex-encode bytes, e.g. for build ids.
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// ID string at the start of an initialized RTT control block (NUL-padded to 16 bytes).
+const RTT_ID: &[u8] = b"SEGGER RTT";
+
+/// Offset of MaxNumUpBuffers (followed by MaxNumDownBuffers) in the RTT control block.
+const RTT_MAX_UP_OFFSET: u64 = 16;
+
+/// Offset of the first buffer descriptor in the RTT control block.
+const RTT_DESCRIPTORS_OFFSET: u64 = 24;
+
+/// Size of one buffer descriptor on a 32-bit target: name, buffer, size, write
+/// offset, read offset, flags.
+const RTT_DESCRIPTOR_WORDS: usize = 6;
+
+/// Largest MaxNumUpBuffers / MaxNumDownBuffers accepted from a control block.
+/// SEGGER's default is 3 of each, so a count past this is a corrupt block.
+const RTT_MAX_BUFFERS: usize = 256;
+
+/// Buffer descriptors of an RTT control block, read in one access.
+///
+/// Descriptors are laid out as MaxNumUpBuffers up descriptors followed by the down
+/// descriptors, so the down array starts after `max_up` entries.
+struct RttDescriptors {
+    max_up: usize,
+    /// `RTT_DESCRIPTOR_WORDS` words per descriptor.
+    words: Vec<u32>,
+}
+
+impl RttDescriptors {
+    /// Descriptor words of up buffer `number`.
+    fn up(&self, number: usize) -> PyResult<&[u32]> {
+        self.get(number)
+    }
+
+    /// Descriptor words of down buffer `number`.
+    fn down(&self, number: usize) -> PyResult<&[u32]> {
+        self.get(self.max_up + number)
+    }
+
+    fn get(&self, index: usize) -> PyResult<&[u32]> {
+        self.words
+            .get(index * RTT_DESCRIPTOR_WORDS..(index + 1) * RTT_DESCRIPTOR_WORDS)
+            .ok_or_else(|| {
+                RttError::new_err(format!(
+                    "RTT buffer descriptor {} is past the end of the control block",
+                    index
+                ))
+            })
+    }
+}
+
+/// Bytes waiting in an RTT buffer, from its descriptor words.
+fn rtt_descriptor_pending(descriptor: &[u32]) -> usize {
+    // Descriptor words: name, buffer, size, write offset, read offset, flags
+    let (size, write, read) = (descriptor[2] as usize, descriptor[3] as usize, descriptor[4] as usize);
+    if write >= read { write - read } else { size.saturating_sub(read) + write }
+}
+
+/// Bytes waiting in up buffer `number` of the RTT control block at `block`.
+fn rtt_up_pending(core: &mut Core, policy: RetryPolicy, block: u64, number: usize) -> PyResult<usize> {
+    let descriptor = block + RTT_DESCRIPTORS_OFFSET + (number * RTT_DESCRIPTOR_WORDS * 4) as u64;
+    Ok(rtt_descriptor_pending(&read_regs(core, policy, descriptor, RTT_DESCRIPTOR_WORDS)?))
+}
+
+/// How full up buffer `number` was when a read of at most `requested` bytes
+/// returned `count`.
+///
+/// A short read drained the buffer, so it held `count`. A read capped at
+/// `requested` below the buffer's capacity can't tell, so the bytes still pending
+/// are added. Best effort: if that extra read fails, `count` is used.
+fn rtt_read_fill(
+    core: &mut Core,
+    policy: RetryPolicy,
+    block: u64,
+    number: usize,
+    count: usize,
+    requested: usize,
+    buffer_size: usize,
+) -> usize {
+    if count < requested || requested + 1 >= buffer_size {
+        return count;
+    }
+    count + rtt_up_pending(core, policy, block, number).unwrap_or(0)
+}
+
+/// True if the RTT control block at `block` still looks like the one attached to:
+/// the ID reads "SEGGER RTT" and it declares `num_up` up channels.
+///
+/// A target reset zeroes the block (it lives in .bss) and the firmware rebuilds it,
+/// possibly with a different layout; until then the ID is missing.
+fn rtt_block_intact(core: &mut Core, policy: RetryPolicy, block: u64, num_up: usize) -> PyResult<bool> {
+    let mut id = [0u8; 16];
+    retry_transient(policy, || core.read(block, &mut id)).map_err(|e| {
+        pyo3::exceptions::PyRuntimeError::new_err(format!(
+            "Failed to read RTT control block at 0x{:08x}: {}",
+            block, e
+        ))
+    })?;
+    if !id.starts_with(RTT_ID) {
+        return Ok(false);
+    }
+    Ok(read_reg(core, policy, block + RTT_MAX_UP_OFFSET)? as usize == num_up)
+}
+
+/// Name of the operating mode encoded in the low two bits of an RTT buffer's flags.
+fn rtt_mode_name(flags: u32) -> &'static str {
+    match flags & 0b11 {
+        0 => "no_block_skip",
+        1 => "no_block_trim",
+        2 => "block_if_full",
+        _ => "invalid",
+    }
+}
+
+/// Classify `address` against a target memory map as "flash", "ram" or "unknown",
+/// with the name of the region it falls in (if the region has one).
+///
+/// Generic regions (boot ROM, peripherals) and addresses outside the map are
+/// "unknown".
+fn classify_address(memory_map: &[MemoryRegion], address: u64) -> (&'static str, Option<String>) {
+    memory_map
+        .iter()
+        .find_map(|region| match region {
+            MemoryRegion::Nvm(r) if r.range.contains(&address) => Some(("flash", r.name.clone())),
+            MemoryRegion::Ram(r) if r.range.contains(&address) => Some(("ram", r.name.clone())),
+            MemoryRegion::Generic(r) if r.range.contains(&address) => Some(("unknown", r.name.clone())),
+            _ => None,
+        })
+        .unwrap_or(("unknown", None))
+}
+
+/// Reject addresses that are not naturally aligned for a `bits`-wide access.
+///
+/// Cortex-M raises a bus fault on misaligned word accesses through the AHB-AP, which
+/// surfaces as an opaque probe error. Checking up front gives a clear ValueError instead.
+fn check_alignment(address: u64, bits: u32) -> PyResult<()> {
+    let bytes = u64::from(bits / 8);
+    if address % bytes != 0 {
+        return Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "address 0x{:08x} not {}-byte aligned for {}-bit access",
+            address, bytes, bits
+        )));
+    }
+    Ok(())
+}
+
+/// Most bytes one read call will buffer on the host. Larger than any MCU's memory,
+/// small enough that a mistyped count is a ValueError rather than an allocation
+/// failure that aborts the interpreter.
+const MAX_READ_BYTES: usize = 256 * 1024 * 1024;
+
+/// Total size of `count` items of `itemsize` bytes, rejecting overflow and reads
+/// larger than `MAX_READ_BYTES`.
+fn check_read_size(count: usize, itemsize: usize) -> PyResult<usize> {
+    count
+        .checked_mul(itemsize)
+        .filter(|&total| total <= MAX_READ_BYTES)
+        .ok_or_else(|| {
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "{} x {} bytes is more than the {} MB a single read may return",
+                count,
+                itemsize,
+                MAX_READ_BYTES / (1024 * 1024)
+            ))
+        })
+}
+
+/// CRC algorithms supported by `crc_memory`.
+///
+/// Both are the reflected 32-bit CRCs with init and final XOR of 0xFFFFFFFF, so they
+/// share one bitwise implementation and differ only in the polynomial.
+#[derive(Clone, Copy)]
+enum CrcAlgorithm {
+    /// CRC-32/ISO-HDLC, as computed by Python's `zlib.crc32` and `binascii.crc32`
+    Crc32,
+    /// CRC-32C (Castagnoli)
+    Crc32c,
+}
+
+impl CrcAlgorithm {
+    fn parse(name: &str) -> PyResult<CrcAlgorithm> {
+        Ok(match name {
+            "crc32" => CrcAlgorithm::Crc32,
+            "crc32c" => CrcAlgorithm::Crc32c,
+            other => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Unknown CRC algorithm '{}'. Expected one of: crc32, crc32c",
+                    other
+                )))
+            }
+        })
+    }
+
+    /// Reflected polynomial.
+    fn polynomial(self) -> u32 {
+        match self {
+            CrcAlgorithm::Crc32 => 0xEDB8_8320,
+            CrcAlgorithm::Crc32c => 0x82F6_3B78,
+        }
+    }
+
+    /// Feed `data` into a running (non-finalized) CRC register.
+    fn update(self, mut crc: u32, data: &[u8]) -> u32 {
+        let poly = self.polynomial();
+        for byte in data {
+            crc ^= u32::from(*byte);
+            for _ in 0..8 {
+                crc = if crc & 1 != 0 { (crc >> 1) ^ poly } else { crc >> 1 };
+            }
+        }
+        crc
+    }
+}
+
+/// What memory/register access does when the core is in lockup (see `lockup_policy`).
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum LockupPolicy {
+    /// Don't check.
+    Ignore,
+    /// Attempt the access and emit a RuntimeWarning on entering lockup.
+    Warn,
+    /// Refuse the access with RuntimeError.
+    Raise,
+}
+
+impl LockupPolicy {
+    fn parse(name: &str) -> PyResult<LockupPolicy> {
+        Ok(match name {
+            "ignore" => LockupPolicy::Ignore,
+            "warn" => LockupPolicy::Warn,
+            "raise" => LockupPolicy::Raise,
+            other => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Unknown lockup_policy '{}'. Expected one of: ignore, warn, raise",
+                    other
+                )))
+            }
+        })
+    }
+}
+
+/// How attach() connects to the target (see `connect_mode`).
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ConnectMode {
+    /// Attach without touching the core's run state.
+    Normal,
+    /// Attach with the reset line asserted, then let the firmware run from reset.
+    UnderReset,
+    /// Attach, then reset the core and keep it halted at the reset vector.
+    HaltAfterReset,
+}
+
+impl ConnectMode {
+    fn parse(name: &str) -> PyResult<ConnectMode> {
+        Ok(match name {
+            "normal" => ConnectMode::Normal,
+            "under_reset" => ConnectMode::UnderReset,
+            "halt_after_reset" => ConnectMode::HaltAfterReset,
+            other => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Unknown connect_mode '{}'. Expected one of: normal, under_reset, halt_after_reset",
+                    other
+                )))
+            }
+        })
+    }
+
+    /// True for the modes that reset the target.
+    fn resets(self) -> bool {
+        matches!(self, ConnectMode::UnderReset | ConnectMode::HaltAfterReset)
+    }
+}
+
+/// RTT polling parameters, picked per probe type by `tune_for_probe()`.
+#[derive(Clone, Copy)]
+struct RttTuning {
+    probe_kind: &'static str,
+    /// Maximum bytes requested from an up channel per read.
+    read_chunk_size: usize,
+    /// Pause between polls that found no data, in the streaming loops.
+    poll_interval: Duration,
+}
+
+impl Default for RttTuning {
+    fn default() -> Self {
+        RttTuning {
+            probe_kind: "generic",
+            read_chunk_size: 4096,
+            poll_interval: Duration::from_millis(1),
+        }
+    }
+}
+
+impl RttTuning {
+    /// Defaults that suit `probe`'s transport. Probes with high per-transfer
+    /// latency (J-Link over USB bulk, ESP USB-JTAG) do best with large, infrequent
+    /// reads; HID CMSIS-DAP moves small packets quickly and prefers smaller reads
+    /// polled more often.
+    fn for_probe(probe: &DebugProbeInfo) -> RttTuning {
+        let (probe_kind, read_chunk_size, poll_ms) = match (probe.vendor_id, probe.product_id) {
+            (0x1366, _) => ("jlink", 16384, 10),
+            (0x0483, _) => ("stlink", 4096, 5),
+            ESP_USB_JTAG => ("esp_usb_jtag", 2048, 10),
+            (0x0403, _) => ("ftdi", 4096, 5),
+            _ if probe.identifier.to_ascii_lowercase().contains("cmsis-dap") => ("cmsis_dap", 1024, 2),
+            _ => return RttTuning::default(),
+        };
+        RttTuning {
+            probe_kind,
+            read_chunk_size,
+            poll_interval: Duration::from_millis(poll_ms),
+        }
+    }
+}
+
+/// RTT layout found by the last successful `start_rtt()`, for `start_rtt_cached()`.
+#[derive(Clone)]
+struct RttCache {
+    address: u64,
+    /// (name, buffer size) of each up channel, in channel order.
+    up_channels: Vec<(Option<String>, usize)>,
+}
+
+impl RttCache {
+    fn of(rtt: &mut Rtt) -> RttCache {
+        RttCache {
+            address: u64::from(rtt.ptr()),
+            up_channels: rtt
+                .up_channels()
+                .iter()
+                .map(|channel| (channel.name().map(str::to_string), channel.buffer_size()))
+                .collect(),
+        }
+    }
+}
+
+/// Bounded ring buffer of the most recent bytes read from one RTT channel.
+struct ChannelHistory {
+    max_bytes: usize,
+    data: VecDeque<u8>,
+}
+
+impl ChannelHistory {
+    /// Append newly read bytes, dropping the oldest ones beyond `max_bytes`.
+    fn push(&mut self, bytes: &[u8]) {
+        self.data.extend(bytes);
+        self.trim();
+    }
+
+    fn trim(&mut self) {
+        let excess = self.data.len().saturating_sub(self.max_bytes);
+        self.data.drain(..excess);
+    }
+}
+
+/// Running read statistics for one RTT up channel.
+struct ChannelStats {
+    first_read: Instant,
+    reads: u64,
+    bytes: u64,
+    /// Reads that found a completely full target buffer (data was likely dropped
+    /// or the firmware blocked while waiting for the host).
+    full_reads: u64,
+}
+
+impl ChannelStats {
+    fn new() -> ChannelStats {
+        ChannelStats {
+            first_read: Instant::now(),
+            reads: 0,
+            bytes: 0,
+            full_reads: 0,
+        }
+    }
+
+    /// Count a read of `count` bytes from a buffer that held `fill` bytes at the
+    /// time (more than `count` when the read was capped by the chunk size).
+    fn record(&mut self, count: usize, fill: usize, buffer_size: usize) {
+        self.reads += 1;
+        self.bytes += count as u64;
+        // An RTT ring buffer holds at most size - 1 bytes
+        if buffer_size > 1 && fill >= buffer_size - 1 {
+            self.full_reads += 1;
         }
     }
+}
+
+/// Magic bytes at the start of a capture log file (format version 1).
+const CAPTURE_LOG_MAGIC: &[u8; 8] = b"EABRTT01";
+
+/// Binary log of RTT traffic; see `ProbeRsSession::start_capture_log` for the format.
+struct CaptureLog {
+    writer: BufWriter<fs::File>,
+    path: String,
+    /// Bytes written to the current file, including the header.
+    file_bytes: u64,
+    /// Rotate once the current file would exceed this size. None = never rotate.
+    max_file_bytes: Option<u64>,
+    /// Files kept in total: the current one plus `max_files - 1` rotated ones.
+    max_files: usize,
+}
+
+/// Size of a capture log record header: channel, timestamp, length.
+const CAPTURE_RECORD_HEADER: u64 = 13;
+
+impl CaptureLog {
+    fn create(path: &str, max_file_bytes: Option<u64>, max_files: usize) -> std::io::Result<CaptureLog> {
+        Ok(CaptureLog {
+            writer: Self::open_file(path)?,
+            path: path.to_string(),
+            file_bytes: CAPTURE_LOG_MAGIC.len() as u64,
+            max_file_bytes,
+            max_files,
+        })
+    }
+
+    fn open_file(path: &str) -> std::io::Result<BufWriter<fs::File>> {
+        let mut writer = BufWriter::new(fs::File::create(path)?);
+        writer.write_all(CAPTURE_LOG_MAGIC)?;
+        Ok(writer)
+    }
+
+    /// Shift `path.1` -> `path.2` and so on (dropping the oldest), move the current
+    /// file to `path.1` and start a fresh `path`. With `max_files == 1` the current
+    /// file is simply truncated.
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.writer.flush()?;
+        let rotated = |n: usize| format!("{}.{}", self.path, n);
+        if self.max_files > 1 {
+            let _ = fs::remove_file(rotated(self.max_files - 1));
+            for n in (1..self.max_files - 1).rev() {
+                let _ = fs::rename(rotated(n), rotated(n + 1));
+            }
+            fs::rename(&self.path, rotated(1))?;
+        }
+        self.writer = Self::open_file(&self.path)?;
+        self.file_bytes = CAPTURE_LOG_MAGIC.len() as u64;
+        Ok(())
+    }
+
+    /// Append one record: channel, host timestamp, length, payload.
+    ///
+    /// Records are never split across files; a record larger than the limit on
+    /// its own still goes into a single (oversized) file.
+    fn write_record(&mut self, channel: usize, data: &[u8]) -> std::io::Result<()> {
+        let record_bytes = CAPTURE_RECORD_HEADER + data.len() as u64;
+        if let Some(max) = self.max_file_bytes {
+            let empty = self.file_bytes == CAPTURE_LOG_MAGIC.len() as u64;
+            if !empty && self.file_bytes + record_bytes > max {
+                self.rotate()?;
+            }
+        }
+        self.file_bytes += record_bytes;
+
+        let timestamp_us = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_micros() as u64;
+        self.writer.write_all(&[channel as u8])?;
+        self.writer.write_all(&timestamp_us.to_le_bytes())?;
+        self.writer.write_all(&(data.len() as u32).to_le_bytes())?;
+        self.writer.write_all(data)
+    }
+}
+
+/// Element type of a [`MemoryBuffer`], with its buffer-protocol format code.
+#[derive(Clone, Copy)]
+enum BufferDtype {
+    U8,
+    U16,
+    U32,
+    F32,
+}
+
+impl BufferDtype {
+    fn parse(name: &str) -> PyResult<BufferDtype> {
+        Ok(match name {
+            "u8" => BufferDtype::U8,
+            "u16" => BufferDtype::U16,
+            "u32" => BufferDtype::U32,
+            "f32" => BufferDtype::F32,
+            other => {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "Unknown dtype '{}'. Expected one of: u8, u16, u32, f32",
+                    other
+                )))
+            }
+        })
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            BufferDtype::U8 => "u8",
+            BufferDtype::U16 => "u16",
+            BufferDtype::U32 => "u32",
+            BufferDtype::F32 => "f32",
+        }
+    }
+
+    fn itemsize(self) -> usize {
+        match self {
+            BufferDtype::U8 => 1,
+            BufferDtype::U16 => 2,
+            BufferDtype::U32 | BufferDtype::F32 => 4,
+        }
+    }
+
+    /// struct-module format code, as expected by the buffer protocol.
+    fn format(self) -> &'static CStr {
+        match self {
+            BufferDtype::U8 => c"B",
+            BufferDtype::U16 => c"H",
+            BufferDtype::U32 => c"I",
+            BufferDtype::F32 => c"f",
+        }
+    }
+}
+
+/// Read-only block of target memory exposing the Python buffer protocol.
+///
+/// Returned by `ProbeRsSession.read_buffer()`. The buffer carries its element type,
+/// so `numpy.asarray(buf)` / `numpy.frombuffer(buf, ...)` / `memoryview(buf)` see
+/// typed items without copying. Items are stored in host byte order.
+#[pyclass(frozen)]
+struct MemoryBuffer {
+    data: Vec<u8>,
+    dtype: BufferDtype,
+    /// Shape and strides for the 1-D buffer view; must outlive any exported view,
+    /// which holds a reference to this object.
+    shape: [isize; 1],
+    strides: [isize; 1],
+}
+
+#[pymethods]
+impl MemoryBuffer {
+    /// Element type: "u8", "u16", "u32" or "f32".
+    #[getter]
+    fn dtype(&self) -> &'static str {
+        self.dtype.name()
+    }
+
+    /// Number of elements.
+    fn __len__(&self) -> usize {
+        self.shape[0] as usize
+    }
+
+    unsafe fn __getbuffer__(slf: Bound<'_, Self>, view: *mut ffi::Py_buffer, flags: c_int) -> PyResult<()> {
+        if view.is_null() {
+            return Err(PyBufferError::new_err("View is null"));
+        }
+        if flags & ffi::PyBUF_WRITABLE == ffi::PyBUF_WRITABLE {
+            return Err(PyBufferError::new_err("MemoryBuffer is read-only"));
+        }
+
+        let this = slf.get();
+        (*view).buf = this.data.as_ptr() as *mut c_void;
+        (*view).len = this.data.len() as isize;
+        (*view).readonly = 1;
+        (*view).itemsize = this.dtype.itemsize() as isize;
+        (*view).format = if flags & ffi::PyBUF_FORMAT == ffi::PyBUF_FORMAT {
+            this.dtype.format().as_ptr() as *mut c_char
+        } else {
+            ptr::null_mut()
+        };
+        (*view).ndim = 1;
+        (*view).shape = if flags & ffi::PyBUF_ND == ffi::PyBUF_ND {
+            this.shape.as_ptr() as *mut isize
+        } else {
+            ptr::null_mut()
+        };
+        (*view).strides = if flags & ffi::PyBUF_STRIDES == ffi::PyBUF_STRIDES {
+            this.strides.as_ptr() as *mut isize
+        } else {
+            ptr::null_mut()
+        };
+        (*view).suboffsets = ptr::null_mut();
+        (*view).internal = ptr::null_mut();
+        // The view keeps this object (and so `data`) alive until it is released
+        (*view).obj = slf.into_any().into_ptr();
+        Ok(())
+    }
+}
+
+/// Handle for a background memory poller started by `ProbeRsSession.watch_memory()`.
+///
+/// The poller stops when `stop()` is called, when a read fails (see `error`), or
+/// when the handle is garbage collected. Only `stop()` waits for the thread to
+/// finish, so call it before the interpreter exits.
+#[pyclass(frozen)]
+struct MemoryWatch {
+    stop: Arc<AtomicBool>,
+    thread: Mutex<Option<std::thread::JoinHandle<()>>>,
+    /// Last value read, and the error that ended polling (if any).
+    state: Arc<Mutex<(Option<u32>, Option<String>)>>,
+}
+
+#[pymethods]
+impl MemoryWatch {
+    /// Stop polling and wait for the background thread to finish.
+    fn stop(&self, py: Python<'_>) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.lock().unwrap().take() {
+            // The thread needs the GIL for its last callback
+            py.allow_threads(|| {
+                let _ = thread.join();
+            });
+        }
+    }
+
+    /// True while the background thread is polling.
+    #[getter]
+    fn is_running(&self) -> bool {
+        self.thread
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|thread| !thread.is_finished())
+    }
+
+    /// Most recent value read, or None before the first read.
+    #[getter]
+    fn value(&self) -> Option<u32> {
+        self.state.lock().unwrap().0
+    }
+
+    /// Why polling stopped on its own (e.g. detached, read failed), or None.
+    #[getter]
+    fn error(&self) -> Option<String> {
+        self.state.lock().unwrap().1.clone()
+    }
+}
+
+impl Drop for MemoryWatch {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+/// A probe-rs session with RTT support.
+///
+/// This class wraps a probe-rs `Session` and provides methods for:
+/// - Attaching to a target chip via a debug probe
+/// - Starting RTT (Real-Time Transfer) for high-speed data streaming
+/// - Reading/writing raw bytes on RTT channels
+/// - Resetting the target
+///
+/// # Thread Safety
+///
+/// The session is wrapped in a Mutex because probe-rs requires exclusive access
+/// to the debug probe. Python's GIL ensures only one thread accesses this at a time,
+/// but we use Mutex for Rust safety guarantees. The class is frozen (all state
+/// sits behind those locks), so background threads can use it without the GIL.
+#[pyclass(frozen)]
+struct ProbeRsSession {
+    /// The active probe-rs session (probe + core state).
+    /// None if not connected.
+    session: Mutex<Option<Session>>,
+
+    /// RTT control block state.
+    /// None until start_rtt() is called.
+    rtt: Mutex<Option<Rtt>>,
+
+    /// Target chip name (e.g., "STM32L476RG", "nRF52840_xxAA").
+    chip: String,
+
+    /// Optional probe selector (serial number or VID:PID).
+    /// If None, uses the first available probe.
+    probe_selector: Option<String>,
+
+    /// While set, the read methods return no data and leave the target's RTT
+    /// buffers untouched (see pause_rtt()).
+    rtt_paused: AtomicBool,
+
+    /// Read statistics per up channel since RTT was started, for check_rtt_health().
+    rtt_stats: Mutex<HashMap<usize, ChannelStats>>,
+
+    /// Bytes read after the last delimiter, per up channel, for rtt_read_frames().
+    partial_frames: Mutex<HashMap<usize, Vec<u8>>>,
+
+    /// Binary capture log that read data is appended to, if one is active.
+    capture_log: Mutex<Option<CaptureLog>>,
+
+    /// The probe picked by the last successful attach(). None before attaching.
+    selected_probe: Mutex<Option<DebugProbeInfo>>,
+
+    /// Host-side history of data read, per up channel with history enabled.
+    history: Mutex<HashMap<usize, ChannelHistory>>,
+
+    /// Optional path to a probe-rs target description (family YAML) that is
+    /// registered before attaching. Used for custom silicon not in the built-in
+    /// registry, e.g. parts with a relocated ROM table / debug base.
+    target_yaml: Option<String>,
+
+    /// If true, writes and resets are refused (see check_writable()).
+    read_only: bool,
+
+    /// IR length of every TAP on the JTAG chain, nearest TDI first. If set,
+    /// attach() uses JTAG and describes the chain to the probe before attaching.
+    scan_chain: Option<Vec<u8>>,
+
+    /// Index into `scan_chain` of the TAP to attach to.
+    jtag_tap: usize,
+
+    /// Reopen the probe once the session has been attached this long (see
+    /// reconnect_if_due()). None = never.
+    max_session: Option<Duration>,
+
+    /// When the current session was attached.
+    attached_at: Mutex<Option<Instant>>,
+
+    /// How core accesses react to a locked-up core (see check_lockup()).
+    lockup_policy: LockupPolicy,
+
+    /// Set once the lockup warning has been emitted, so it fires once per lockup.
+    lockup_warned: AtomicBool,
+
+    /// RTT read chunk size and poll interval (see tune_for_probe()).
+    rtt_tuning: Mutex<RttTuning>,
+
+    /// Set when the RTT control block no longer matches the one attached to,
+    /// typically after a target reset (see check_rtt_valid()).
+    rtt_desync: AtomicBool,
+
+    /// Retries for transient access errors (constructor `transient_retries`).
+    retry_policy: RetryPolicy,
+
+    /// Control block address and channel layout from the last start_rtt(). Kept
+    /// across detach() so a reconnect can use start_rtt_cached().
+    rtt_cache: Mutex<Option<RttCache>>,
+
+    /// Set when an access failed because the probe disappeared; cleared by attach()
+    /// (see mark_disconnected()).
+    probe_disconnected: AtomicBool,
+}
+
+#[pymethods]
+impl ProbeRsSession {
+    /// Create a new probe-rs session for the specified chip.
+    ///
+    /// Args:
+    ///     chip: Target chip name (e.g., "STM32L476RG", "nRF52840_xxAA"). May be a
+    ///         prefix of a probe-rs variant name; if the prefix matches variants
+    ///         with different memory maps, attach() asks for the full variant name.
+    ///     probe_selector: Optional probe selector string: "VID:PID" or
+    ///         "VID:PID:SERIAL" in hex (e.g. "303a:1001" for the ESP32 built-in
+    ///         USB-JTAG), or a serial number / identifier substring. If omitted,
+    ///         the first probe is used — except for ESP32 chips, where the
+    ///         built-in USB-JTAG is preferred when present.
+    ///     target_yaml: Optional path to a probe-rs target description YAML to
+    ///         register before attaching. This is for custom silicon: parts whose
+    ///         ROM table or debug components sit at a non-standard address that
+    ///         auto-discovery misses can set `debug_base` (and `cti_base`) under
+    ///         the core's `core_access_options` in this file. `chip` must then
+    ///         name a variant defined in the YAML.
+    ///     read_only: If True, every method that writes target memory or resets
+    ///         the target (write_mem*, rtt_write, reset, ...) raises PermissionError
+    ///         before touching the probe, and the probe is opened with minimal
+    ///         permissions (no chip erase). For inspecting production devices.
+    ///         RTT reads are still allowed: they only advance the channel's read
+    ///         pointer in the control block, as the RTT protocol requires.
+    ///     scan_chain: Optional list of instruction register lengths, one per TAP on
+    ///         a JTAG daisy chain, in chain order starting at the TAP nearest the
+    ///         probe's TDI. When given, attach() selects the JTAG protocol and
+    ///         configures the chain before attaching, so a chip that shares the
+    ///         chain with other devices (FPGA, second MCU) can be reached.
+    ///     jtag_tap: Index into scan_chain of the TAP to attach to (default 0).
+    ///     max_session_seconds: Optional maximum session lifetime. Once the session
+    ///         has been attached this long, the next RTT read first closes the
+    ///         probe, reopens it and reattaches (RTT at the same control block), to
+    ///         shed USB/probe state that degrades over multi-hour runs on some
+    ///         probe/OS combinations. Channel positions live in target memory, and
+    ///         host-side state (history, statistics, capture log) is kept, so no
+    ///         data is lost or repeated.
+    ///     lockup_policy: What memory and register access does when the core is in
+    ///         lockup (DHCSR.S_LOCKUP, e.g. a fault inside the HardFault handler).
+    ///         Debug access usually still works then, and reading RAM after a
+    ///         lockup is exactly what crash analysis needs, so the default "warn"
+    ///         attempts the access and emits a RuntimeWarning once per lockup.
+    ///         "raise" refuses with RuntimeError; "ignore" skips the check, which
+    ///         saves one DHCSR read per call. Cortex-M only.
+    ///     transient_retries: How many times a memory or register access is retried
+    ///         after a transient error (WAIT/no-ACK response or timeout) before it
+    ///         fails (default 3). probe-rs doesn't expose its DAP-level retry
+    ///         settings, so this is the knob for noisy setups (long leads, no
+    ///         common ground) where accesses fail intermittently. FAULT responses
+    ///         are never retried.
+    ///     transient_retry_delay_ms: Pause between those retries (default 2).
+    ///
+    /// Returns:
+    ///     ProbeRsSession instance (not yet connected — call attach() next)
+    ///
+    /// Raises:
+    ///     ValueError: If jtag_tap is out of range for scan_chain,
+    ///         lockup_policy is unknown, or max_session_seconds is not a positive
+    ///         number
+    ///
+    /// Example:
+    ///     >>> session = ProbeRsSession(chip="STM32L476RG")
+    ///     >>> session = ProbeRsSession(chip="nRF52840_xxAA", probe_selector="0483:374b")
+    ///     >>> session = ProbeRsSession(chip="ACME_SOC1", target_yaml="targets/acme_soc1.yaml")
+    ///     >>> session = ProbeRsSession(chip="nRF52840_xxAA", read_only=True)
+    ///     >>> session = ProbeRsSession(chip="STM32F407VG", scan_chain=[6, 4], jtag_tap=1)
+    ///     >>> session = ProbeRsSession(chip="nRF52840_xxAA", max_session_seconds=30 * 60)
+    ///     >>> session = ProbeRsSession(chip="STM32L476RG", lockup_policy="raise")
+    ///     >>> session = ProbeRsSession(chip="STM32L476RG", transient_retries=10)
+    #[new]
+    #[pyo3(signature = (
+        chip,
+        probe_selector=None,
+        target_yaml=None,
+        read_only=false,
+        scan_chain=None,
+        jtag_tap=0,
+        max_session_seconds=None,
+        lockup_policy="warn",
+        transient_retries=3,
+        transient_retry_delay_ms=2
+    ))]
+    fn new(
+        chip: String,
+        probe_selector: Option<String>,
+        target_yaml: Option<String>,
+        read_only: bool,
+        scan_chain: Option<Vec<u8>>,
+        jtag_tap: usize,
+        max_session_seconds: Option<f64>,
+        lockup_policy: &str,
+        transient_retries: usize,
+        transient_retry_delay_ms: u64,
+    ) -> PyResult<Self> {
+        let lockup_policy = LockupPolicy::parse(lockup_policy)?;
+        let max_session = max_session_seconds
+            .map(|seconds| {
+                Duration::try_from_secs_f64(seconds)
+                    .ok()
+                    .filter(|duration| !duration.is_zero())
+                    .ok_or_else(|| {
+                        pyo3::exceptions::PyValueError::new_err(format!(
+                            "max_session_seconds must be a positive number, got {}",
+                            seconds
+                        ))
+                    })
+            })
+            .transpose()?;
+        if let Some(chain) = &scan_chain {
+            if jtag_tap >= chain.len() {
+                return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                    "jtag_tap {} out of range for a scan chain of {} TAP(s)",
+                    jtag_tap,
+                    chain.len()
+                )));
+            }
+        }
+
+        Ok(Self {
+            session: Mutex::new(None),
+            rtt: Mutex::new(None),
+            rtt_paused: AtomicBool::new(false),
+            rtt_stats: Mutex::new(HashMap::new()),
+            partial_frames: Mutex::new(HashMap::new()),
+            capture_log: Mutex::new(None),
+            selected_probe: Mutex::new(None),
+            history: Mutex::new(HashMap::new()),
+            chip,
+            probe_selector,
+            target_yaml,
+            read_only,
+            scan_chain,
+            jtag_tap,
+            max_session,
+            attached_at: Mutex::new(None),
+            lockup_policy,
+            lockup_warned: AtomicBool::new(false),
+            rtt_tuning: Mutex::new(RttTuning::default()),
+            rtt_desync: AtomicBool::new(false),
+            retry_policy: RetryPolicy {
+                retries: transient_retries,
+                delay: Duration::from_millis(transient_retry_delay_ms),
+            },
+            rtt_cache: Mutex::new(None),
+            probe_disconnected: AtomicBool::new(false),
+        })
+    }
+
+    /// Attach to the target chip via a debug probe.
+    ///
+    /// This:
+    /// 1. Lists available debug probes
+    /// 2. Opens the first probe (or the one matching probe_selector)
+    /// 3. Attaches to the target chip via SWD
+    /// 4. Leaves the core running, reset or halted according to connect_mode
+    ///
+    /// Args:
+    ///     connect_mode: How to connect (default "normal"):
+    ///         "normal": attach without touching the core's run state; a core left
+    ///             halted (e.g. by another tool or a fault) stays halted
+    ///         "under_reset": hold the target in reset while attaching, then let it
+    ///             run. Needed when the firmware disables the debug pins or sleeps
+    ///             too deeply to attach to
+    ///         "halt_after_reset": attach, then reset and halt at the reset vector
+    ///     swd_retry: If True and the first attach fails, reopen the probe and
+    ///         attach once more with the SWD wire protocol selected explicitly
+    ///         instead of the probe's default. This is a plain second attempt, not
+    ///         a recovery procedure: no extra line reset or JTAG-to-SWD sequence is
+    ///         sent beyond what a normal attach does. It helps with a probe whose
+    ///         default is JTAG. Can't be combined with the session's scan_chain,
+    ///         which needs JTAG.
+    ///     auto_rtt: If True, start RTT right after attaching, as start_rtt() would
+    ///         (using elf_path to find the control block, or the RAM scan). If that
+    ///         fails, the session stays attached without RTT and a RuntimeWarning
+    ///         carries the reason; attach() itself still succeeds.
+    ///     elf_path: ELF file used to locate the control block when auto_rtt is set
+    ///
+    /// Returns:
+    ///     int | None: Number of up channels if auto_rtt started RTT, else None
+    ///
+    /// Raises:
+    ///     RuntimeError: If no probe found, chip not recognized, or connection fails
+    ///     ValueError: If the chip name matches several variants with different
+    ///         memory maps (the error lists the fully-qualified names to use),
+    ///         connect_mode is unknown, or swd_retry is set on a session with a
+    ///         scan_chain
+    ///     PermissionError: If connect_mode resets the target on a read-only session
+    ///
+    /// Example:
+    ///     >>> session.attach()
+    ///     >>> session.attach(connect_mode="halt_after_reset")  # stop before main()
+    ///     >>> session.attach(swd_retry=True)  # probe defaults to JTAG
+    ///     >>> channels = session.attach(auto_rtt=True, elf_path="build/zephyr/zephyr.elf")
+    #[pyo3(signature = (swd_retry=false, auto_rtt=false, elf_path=None, connect_mode="normal"))]
+    fn attach(
+        &self,
+        py: Python<'_>,
+        swd_retry: bool,
+        auto_rtt: bool,
+        elf_path: Option<String>,
+        connect_mode: &str,
+    ) -> PyResult<Option<usize>> {
+        let mode = ConnectMode::parse(connect_mode)?;
+        if mode.resets() {
+            self.check_writable(&format!("attach(connect_mode='{}')", connect_mode))?;
+        }
+        let connect_mode = mode;
+        if swd_retry && self.scan_chain.is_some() {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "swd_retry can't be combined with scan_chain (the scan chain is JTAG-only)",
+            ));
+        }
+        self.register_target_yaml()?;
+        self.check_chip_unambiguous()?;
+        let probe_info = self.select_probe()?;
+
+        // Attach to target with SWD
+        let under_reset = connect_mode == ConnectMode::UnderReset;
+        let mut session = match self.open_and_attach(&probe_info, false, under_reset) {
+            Ok(session) => session,
+            Err(first) if swd_retry => {
+                self.open_and_attach(&probe_info, true, under_reset).map_err(|retry| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "{} (retry with SWD forced also failed: {})",
+                        first.value(py),
+                        retry.value(py)
+                    ))
+                })?
+            }
+            Err(e) => return Err(e),
+        };
+
+        if connect_mode != ConnectMode::Normal {
+            let mut core = session.core(0).map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to access core: {}", e))
+            })?;
+            if connect_mode == ConnectMode::HaltAfterReset {
+                core.reset_and_halt(Duration::from_millis(500)).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!("Reset and halt failed: {}", e))
+                })?;
+            } else if core.core_halted().map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to read core status: {}", e))
+            })? {
+                core.run().map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to resume core: {}", e))
+                })?;
+            }
+        }
+
+        // Store session
+        *self.session.lock().unwrap() = Some(session);
+        *self.selected_probe.lock().unwrap() = Some(probe_info);
+        self.probe_disconnected.store(false, Ordering::Relaxed);
+        *self.attached_at.lock().unwrap() = Some(Instant::now());
+
+        if !auto_rtt {
+            return Ok(None);
+        }
+        match self.start_rtt(elf_path, None) {
+            Ok(num_up) => Ok(Some(num_up)),
+            Err(e) => {
+                let message = CString::new(format!("Attached, but RTT did not start: {}", e.value(py)))
+                    .unwrap_or_default();
+                PyErr::warn(py, &py.get_type::<pyo3::exceptions::PyRuntimeWarning>(), &message, 1)?;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Start RTT on the target.
+    ///
+    /// This finds the RTT control block (a struct placed by the firmware that
+    /// describes RTT channel buffers) and initializes RTT for reading/writing channels.
+    ///
+    /// Three methods to locate the control block (in priority order):
+    /// Priority order (if both parameters provided, block_address takes precedence):
+    /// 1. If `block_address` provided: Use that exact address (fastest, elf_path ignored)
+    /// 2. If `elf_path` provided: Read _SEGGER_RTT symbol from ELF (reliable)
+    /// 3. Otherwise: Scan all RAM for the control block signature (slow, may fail)
+    ///
+    /// Args:
+    ///     elf_path: Optional path to ELF file (e.g., "build/zephyr/zephyr.elf").
+    ///         probe-rs will read the _SEGGER_RTT symbol address from the ELF.
+    ///         This is the RECOMMENDED approach - always works if firmware has RTT.
+    ///     block_address: Optional RTT control block address (e.g., 0x20001010).
+    ///         If provided, skips ELF parsing and RAM scanning (elf_path is ignored).
+    ///         Use this for maximum speed if you know the exact address.
+    ///
+    /// Returns:
+    ///     int: Number of up (target→host) channels found
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached, or RTT control block not found
+    ///
+    /// Example:
+    ///     >>> # RECOMMENDED: Use ELF to find RTT symbol (works with any probe)
+    ///     >>> num_channels = session.start_rtt(elf_path="build/zephyr/zephyr.elf")
+    ///     >>> # Fallback: Auto-scan RAM (may fail with some probes/targets)
+    ///     >>> num_channels = session.start_rtt()
+    ///     >>> # Fastest: Use known address
+    ///     >>> num_channels = session.start_rtt(block_address=0x20001010)
+    #[pyo3(signature = (elf_path=None, block_address=None))]
+    fn start_rtt(&self, elf_path: Option<String>, block_address: Option<u64>) -> PyResult<usize> {
+        // The RAM scan needs RAM regions from the target description to scan
+        let has_ram = self
+            .memory_map()?
+            .iter()
+            .any(|region| matches!(region, MemoryRegion::Ram(_)));
+
+        // Determine RTT control block address (priority: explicit > ELF symbol > RAM scan)
+        let rtt_address = if let Some(addr) = block_address {
+            // Priority 1: Explicit address provided (fastest)
+            Some(addr)
+        } else if let Some(ref elf) = elf_path {
+            // Priority 2: Read _SEGGER_RTT symbol from ELF
+            match find_rtt_symbol(elf)? {
+                Some(addr) => Some(addr),
+                None => {
+                    return Err(pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "_SEGGER_RTT symbol not found in ELF file '{}'.\n\
+                         Make sure firmware was built with RTT enabled (CONFIG_USE_SEGGER_RTT=y for Zephyr)",
+                        elf
+                    )));
+                }
+            }
+        } else {
+            // Priority 3: Will scan RAM (may fail)
+            None
+        };
+        if rtt_address.is_none() && !has_ram {
+            return Err(pyo3::exceptions::PyRuntimeError::new_err(format!(
+                "Cannot scan for the RTT control block: the probe-rs target description for '{}'\n\
+                 has no RAM regions. Use start_rtt(elf_path='build/zephyr/zephyr.elf') or\n\
+                 start_rtt(block_address=0x...) to give the control block location directly.",
+                self.chip
+            )));
+        }
+
+        // Attach to RTT control block
+        let mut rtt = self.with_core(|core| {
+            if let Some(addr) = rtt_address {
+                // Use known address (from explicit param or ELF symbol)
+                Rtt::attach_at(core, addr).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "RTT control block not found at 0x{:08x}: {}.\n\
+                         The address is correct but the control block may not be initialized yet.\n\
+                         Make sure firmware has called SEGGER_RTT_Init() or rtt_init!() before connecting.",
+                        addr, e
+                    ))
+                })
+            } else {
+                // Auto-scan RAM regions (slowest, may fail with some probes)
+                Rtt::attach(core).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "RTT control block not found via RAM scan: {}.\n\
+                         RECOMMENDED FIX: Use start_rtt(elf_path='build/zephyr/zephyr.elf') instead.\n\
+                         This reads the _SEGGER_RTT symbol address from your ELF file, which is\n\
+                         much more reliable than scanning (especially with ST-Link probes).",
+                        e
+                    ))
+                })
+            }
+        })?;
+
+        let num_up = rtt.up_channels().len();
+
+        // Store RTT state
+        *self.rtt_cache.lock().unwrap() = Some(RttCache::of(&mut rtt));
+        *self.rtt.lock().unwrap() = Some(rtt);
+        self.reset_channel_state();
+        self.rtt_desync.store(false, Ordering::Relaxed);
+
+        Ok(num_up)
+    }
+
+    /// Start RTT at the control block found by the last start_rtt(), skipping the
+    /// ELF parse and RAM scan.
+    ///
+    /// For fast reconnects (soak tests, flaky probes): after detach() and attach(),
+    /// this attaches straight at the cached address. The block's ID and up-channel
+    /// count are checked first, and the channel names and buffer sizes must match
+    /// the cached ones, so a reflashed firmware with a different layout is caught
+    /// rather than misread.
+    ///
+    /// Returns:
+    ///     int: Number of up channels
+    ///
+    /// Raises:
+    ///     RttError: If the control block is missing or its layout no longer
+    ///         matches the cache (call start_rtt() to look it up again)
+    ///     RuntimeError: If not attached, start_rtt() never succeeded on this
+    ///         session, or a read fails
+    ///
+    /// Example:
+    ///     >>> session.start_rtt(elf_path="build/zephyr/zephyr.elf")
+    ///     >>> session.detach()
+    ///     >>> session.attach()
+    ///     >>> session.start_rtt_cached()
+    fn start_rtt_cached(&self) -> PyResult<usize> {
+        // Cloned so the cache lock isn't held while with_core() takes the session lock
+        let cache = self.rtt_cache.lock().unwrap().clone().ok_or_else(|| {
+            pyo3::exceptions::PyRuntimeError::new_err("No cached RTT layout. Call start_rtt() first.")
+        })?;
+
+        let mut rtt = self.with_core(|core| {
+            if !rtt_block_intact(core, self.retry_policy, cache.address, cache.up_channels.len())? {
+                return Err(RttError::new_err(format!(
+                    "RTT control block at 0x{:08x} is missing or has a different number of up \
+                     channels than cached. Call start_rtt() to locate it again.",
+                    cache.address
+                )));
+            }
+            Rtt::attach_at(core, cache.address).map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "RTT attach at cached address 0x{:08x} failed: {}",
+                    cache.address, e
+                ))
+            })
+        })?;
+
+        if RttCache::of(&mut rtt).up_channels != cache.up_channels {
+            return Err(RttError::new_err(format!(
+                "RTT channel layout at 0x{:08x} differs from the cached one (firmware changed?). \
+                 Call start_rtt() to locate it again.",
+                cache.address
+            )));
+        }
+
+        let num_up = rtt.up_channels().len();
+        *self.rtt.lock().unwrap() = Some(rtt);
+        self.reset_channel_state();
+        self.rtt_desync.store(false, Ordering::Relaxed);
+
+        Ok(num_up)
+    }
+
+    /// Start RTT, waiting for the firmware to initialize the control block.
+    ///
+    /// Right after a reset, `start_rtt()` can race the firmware's RTT init and find
+    /// an uninitialized control block. This polls until the block's ID field reads
+    /// "SEGGER RTT" and then attaches, releasing the GIL between tries. Without an
+    /// address (no elf_path / block_address), the RAM scan is retried instead.
+    ///
+    /// Args:
+    ///     elf_path: Optional ELF path to read the _SEGGER_RTT address from
+    ///     block_address: Optional control block address (takes precedence)
+    ///     timeout_ms: How long to keep trying (default 5000)
+    ///
+    /// Returns:
+    ///     int: Number of up channels found
+    ///
+    /// Raises:
+    ///     TimeoutError: If the control block isn't ready before the timeout
+    ///     RuntimeError: If not attached, or a memory read fails
+    ///     ValueError: If the _SEGGER_RTT symbol is missing from the ELF
+    ///
+    /// Example:
+    ///     >>> session.reset()
+    ///     >>> session.wait_for_rtt(elf_path="build/zephyr/zephyr.elf", timeout_ms=2000)
+    #[pyo3(signature = (elf_path=None, block_address=None, timeout_ms=5000))]
+    fn wait_for_rtt(
+        &self,
+        py: Python<'_>,
+        elf_path: Option<String>,
+        block_address: Option<u64>,
+        timeout_ms: u64,
+    ) -> PyResult<usize> {
+        const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+        let address = match (block_address, elf_path) {
+            (Some(addr), _) => Some(addr),
+            (None, Some(elf)) => Some(require_symbol(&elf, "_SEGGER_RTT")?),
+            (None, None) => None,
+        };
+        let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+
+        loop {
+            // Taken before the attempt, so a scan that fails at the deadline
+            // still reports the timeout
+            let timed_out = Instant::now() >= deadline;
+            let ready = match address {
+                Some(addr) => self.with_core(|core| {
+                    let mut id = [0u8; 16];
+                    retry_transient(self.retry_policy, || core.read(addr, &mut id)).map_err(|e| {
+                        pyo3::exceptions::PyRuntimeError::new_err(format!(
+                            "Failed to read RTT control block at 0x{:08x}: {}",
+                            addr, e
+                        ))
+                    })?;
+                    Ok(id.starts_with(RTT_ID))
+                })?,
+                None => true,
+            };
+
+            let mut scan_error = None;
+            if ready {
+                match self.start_rtt(None, address) {
+                    Ok(num_up) => return Ok(num_up),
+                    Err(e) if e.is_instance_of::<ProbeDisconnectedError>(py) => return Err(e),
+                    // The scan path can only find out by trying
+                    Err(e) if address.is_none() => scan_error = Some(e),
+                    Err(e) => return Err(e),
+                }
+            }
+
+            if timed_out {
+                let last_error = scan_error
+                    .map(|e| format!("\nLast scan error: {}", e.value(py)))
+                    .unwrap_or_default();
+                return Err(pyo3::exceptions::PyTimeoutError::new_err(format!(
+                    "RTT control block not initialized after {} ms.\n\
+                     Make sure firmware calls SEGGER_RTT_Init() or rtt_init!() early in startup.{}",
+                    timeout_ms, last_error
+                )));
+            }
+            py.allow_threads(|| std::thread::sleep(POLL_INTERVAL));
+            py.check_signals()?;
+        }
+    }
+
+    /// Drop the current RTT handle and locate the control block again.
+    ///
+    /// Use this when reads start returning garbage or nothing after an unexpected
+    /// target reset: the firmware re-initializes the control block and the handle
+    /// from `start_rtt()` goes stale. The probe session stays attached.
+    ///
+    /// Args:
+    ///     elf_path: Optional ELF path to re-read the _SEGGER_RTT symbol from.
+    ///         If omitted, RAM is scanned for the control block (see `start_rtt()`).
+    ///
+    /// Returns:
+    ///     int: Number of up channels found after resyncing
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached, or the control block can't be found
+    ///
+    /// Example:
+    ///     >>> session.resync_rtt(elf_path="build/zephyr/zephyr.elf")
+    #[pyo3(signature = (elf_path=None))]
+    fn resync_rtt(&self, elf_path: Option<String>) -> PyResult<usize> {
+        *self.rtt.lock().unwrap() = None;
+        self.start_rtt(elf_path, None)
+    }
+
+    /// Read raw bytes from an RTT up (target→host) channel.
+    ///
+    /// Non-blocking: returns empty bytes if no data available.
+    ///
+    /// Args:
+    ///     channel: RTT up channel index (0-based)
+    ///
+    /// Returns:
+    ///     bytes: Raw data from the channel (may be empty)
+    ///
+    /// Raises:
+    ///     RuntimeError: If RTT not started or channel doesn't exist
+    ///
+    /// Example:
+    ///     >>> data = session.rtt_read(channel=0)
+    ///     >>> if data:
+    ///     ...     print(f"Received {len(data)} bytes")
+    fn rtt_read(&self, channel: usize) -> PyResult<Py<PyBytes>> {
+        let buffer = self.read_channel(channel)?;
+
+        // Return as Python bytes
+        Python::with_gil(|py| Ok(PyBytes::new(py, &buffer).into()))
+    }
+
+    /// Like `rtt_read()`, but returns None for a channel the firmware doesn't have.
+    ///
+    /// Lets generic monitoring code loop over a fixed channel range without
+    /// crashing when the firmware configures fewer up channels than expected.
+    /// Other failures (not attached, RTT not started, read errors) still raise.
+    ///
+    /// Args:
+    ///     channel: RTT up channel index (0-based)
+    ///
+    /// Returns:
+    ///     bytes | None: Data read (may be empty), or None if the channel doesn't exist
+    ///
+    /// Example:
+    ///     >>> for ch in range(4):
+    ///     ...     data = session.try_rtt_read(ch)
+    ///     ...     if data:
+    ///     ...         print(ch, data)
+    fn try_rtt_read(&self, channel: usize) -> PyResult<Option<Py<PyBytes>>> {
+        let num_up = self
+            .rtt
+            .lock()
+            .unwrap()
+            .as_mut()
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("RTT not started. Call start_rtt() first."))?
+            .up_channels()
+            .len();
+        if channel >= num_up {
+            return Ok(None);
+        }
+        self.rtt_read(channel).map(Some)
+    }
+
+    /// Read from an up channel and return the complete delimiter-terminated frames.
+    ///
+    /// For framed protocols (newline-terminated records, COBS or SLIP-style
+    /// telemetry with a 0x00 / 0xC0 boundary). Data is reassembled across reads:
+    /// bytes after the last delimiter are kept per channel and prepended to the
+    /// next read, so a frame split between two reads comes back whole. The
+    /// delimiter itself is not included, and two adjacent delimiters yield an
+    /// empty frame. The pending partial frame is discarded when RTT is restarted.
+    ///
+    /// If no delimiter arrives for more than `max_frame_len` bytes (wrong
+    /// delimiter, or a binary stream), the pending bytes are dropped with a
+    /// RuntimeWarning rather than buffered without bound.
+    ///
+    /// Args:
+    ///     channel: RTT up channel index (0-based)
+    ///     delimiter: Frame delimiter byte (default 0x0A, newline)
+    ///     max_frame_len: Longest partial frame kept between reads (default 65536)
+    ///
+    /// Returns:
+    ///     list[bytes]: Complete frames, oldest first (empty if none completed)
+    ///
+    /// Raises:
+    ///     RuntimeError: If RTT not started or channel doesn't exist
+    ///
+    /// Example:
+    ///     >>> for frame in session.rtt_read_frames(channel=1, delimiter=0x00):
+    ///     ...     packet = cobs.decode(frame)
+    #[pyo3(signature = (channel, delimiter=b'\n', max_frame_len=65536))]
+    fn rtt_read_frames<'py>(
+        &self,
+        py: Python<'py>,
+        channel: usize,
+        delimiter: u8,
+        max_frame_len: usize,
+    ) -> PyResult<Bound<'py, PyList>> {
+        let data = self.read_channel(channel)?;
+
+        let frames = PyList::empty(py);
+        let partial_len = {
+            let mut partial_frames = self.partial_frames.lock().unwrap();
+            let pending = partial_frames.entry(channel).or_default();
+            // Everything already pending is delimiter-free, so only the new bytes
+            // need scanning
+            let scanned = pending.len();
+            pending.extend_from_slice(&data);
+
+            let mut start = 0;
+            for (index, _) in pending.iter().enumerate().skip(scanned).filter(|(_, &b)| b == delimiter) {
+                frames.append(PyBytes::new(py, &pending[start..index]))?;
+                start = index + 1;
+            }
+            pending.drain(..start);
+
+            let partial_len = pending.len();
+            if partial_len > max_frame_len {
+                pending.clear();
+            }
+            partial_len
+        };
+
+        if partial_len > max_frame_len {
+            let message = CString::new(format!(
+                "Dropped {} bytes on channel {} with no 0x{:02x} delimiter (max_frame_len={})",
+                partial_len, channel, delimiter, max_frame_len
+            ))
+            .unwrap_or_default();
+            PyErr::warn(py, &py.get_type::<pyo3::exceptions::PyRuntimeWarning>(), &message, 1)?;
+        }
+        Ok(frames)
+    }
+
+    /// Keep a bounded host-side history of the data read from an up channel.
+    ///
+    /// Every subsequent read of `channel` (through any of the read methods) is
+    /// appended to a ring buffer holding the most recent `max_bytes` bytes, so a
+    /// log UI can offer scrollback without buffering in Python. Only data that was
+    /// actually read is recorded. Calling again resizes the buffer (keeping the
+    /// newest bytes); `max_bytes=0` disables history for the channel.
+    ///
+    /// Args:
+    ///     channel: RTT up channel index (0-based)
+    ///     max_bytes: Maximum number of bytes to retain
+    ///
+    /// Example:
+    ///     >>> session.set_history(channel=0, max_bytes=64 * 1024)
+    fn set_history(&self, channel: usize, max_bytes: usize) {
+        let mut history = self.history.lock().unwrap();
+        if max_bytes == 0 {
+            history.remove(&channel);
+            return;
+        }
+        let entry = history.entry(channel).or_insert_with(|| ChannelHistory {
+            max_bytes,
+            data: VecDeque::new(),
+        });
+        entry.max_bytes = max_bytes;
+        entry.trim();
+    }
+
+    /// Get the retained history for a channel (see `set_history()`).
+    ///
+    /// Args:
+    ///     channel: RTT up channel index (0-based)
+    ///
+    /// Returns:
+    ///     bytes: Oldest-to-newest retained data (empty if history is not enabled)
+    ///
+    /// Example:
+    ///     >>> scrollback = session.get_history(channel=0)
+    fn get_history(&self, channel: usize) -> Py<PyBytes> {
+        let history = self.history.lock().unwrap();
+        let data: Vec<u8> = history
+            .get(&channel)
+            .map(|h| h.data.iter().copied().collect())
+            .unwrap_or_default();
+        Python::with_gil(|py| PyBytes::new(py, &data).into())
+    }
+
+    /// Stream an up channel until a pass/fail pattern appears or a timeout expires.
+    ///
+    /// The CI test-runner primitive: "run, watch for PASS/FAIL, or give up". The
+    /// channel is polled in Rust with the GIL released, and each complete line
+    /// (terminated by `\n`) is checked against the patterns as plain substrings.
+    /// Fail patterns are checked before pass patterns on the same line.
+    ///
+    /// Args:
+    ///     channel: RTT up channel index (0-based)
+    ///     pass_patterns: Substrings that mean the run passed (e.g. ["PASS", "All tests passed"])
+    ///     fail_patterns: Substrings that mean the run failed (e.g. ["FAIL", "ASSERT"])
+    ///     timeout_ms: Give up after this many milliseconds
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         outcome: str "passed", "failed" or "timeout"
+    ///         matched_line: str | None the line that matched a pattern
+    ///         matched_pattern: str | None the pattern that matched
+    ///         duration_ms: float time until the match (or the timeout)
+    ///         captured: bytes everything read from the channel during the call
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached, RTT not started, or a read fails
+    ///     KeyboardInterrupt: If interrupted with Ctrl-C
+    ///
+    /// Example:
+    ///     >>> result = session.monitor(0, ["PASS"], ["FAIL"], timeout_ms=30000)
+    ///     >>> assert result["outcome"] == "passed", result["captured"].decode()
+    fn monitor<'py>(
+        &self,
+        py: Python<'py>,
+        channel: usize,
+        pass_patterns: Vec<String>,
+        fail_patterns: Vec<String>,
+        timeout_ms: u64,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let start = Instant::now();
+        let timeout = Duration::from_millis(timeout_ms);
+
+        let mut captured = Vec::new();
+        // Start of the line currently being accumulated, as an offset into `captured`
+        let mut line_start = 0;
+        let mut result: Option<(&str, String, String)> = None;
+
+        'poll: while start.elapsed() < timeout {
+            let data = py.allow_threads(|| self.read_channel(channel))?;
+            if data.is_empty() {
+                let poll_interval = self.rtt_tuning.lock().unwrap().poll_interval;
+                py.allow_threads(|| std::thread::sleep(poll_interval));
+                py.check_signals()?;
+                continue;
+            }
+            captured.extend_from_slice(&data);
+
+            while let Some(newline) = captured[line_start..].iter().position(|&b| b == b'\n') {
+                let line = String::from_utf8_lossy(&captured[line_start..line_start + newline])
+                    .trim_end_matches('\r')
+                    .to_string();
+                line_start += newline + 1;
+
+                let matched = fail_patterns
+                    .iter()
+                    .find(|p| line.contains(p.as_str()))
+                    .map(|p| ("failed", p))
+                    .or_else(|| {
+                        pass_patterns
+                            .iter()
+                            .find(|p| line.contains(p.as_str()))
+                            .map(|p| ("passed", p))
+                    });
+                if let Some((outcome, pattern)) = matched {
+                    result = Some((outcome, line, pattern.clone()));
+                    break 'poll;
+                }
+            }
+        }
+
+        let duration_ms = start.elapsed().as_secs_f64() * 1000.0;
+        let (outcome, matched_line, matched_pattern) = match result {
+            Some((outcome, line, pattern)) => (outcome, Some(line), Some(pattern)),
+            None => ("timeout", None, None),
+        };
+
+        let dict = PyDict::new(py);
+        dict.set_item("outcome", outcome)?;
+        dict.set_item("matched_line", matched_line)?;
+        dict.set_item("matched_pattern", matched_pattern)?;
+        dict.set_item("duration_ms", duration_ms)?;
+        dict.set_item("captured", PyBytes::new(py, &captured))?;
+        Ok(dict)
+    }
+
+    /// Print an up channel to stdout until a duration elapses or Ctrl-C.
+    ///
+    /// The "just show me the logs" mode: data is decoded as UTF-8 (invalid bytes
+    /// replaced) and written to `sys.stdout`, flushed after every line, so it shows
+    /// up in Jupyter and respects redirection (e.g. contextlib.redirect_stdout).
+    /// A line split across reads is held back until its newline arrives, and any
+    /// unterminated tail is printed on return. The channel is polled with the GIL
+    /// released, so other Python threads keep running. Ctrl-C ends the call
+    /// normally instead of raising.
+    ///
+    /// Args:
+    ///     channel: RTT up channel index (0-based)
+    ///     duration_ms: Stop after this many milliseconds (default: run until Ctrl-C)
+    ///
+    /// Returns:
+    ///     int: Number of bytes read from the channel
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached, RTT not started, or a read fails
+    ///     Exception: Whatever sys.stdout.write() or flush() raises
+    ///
+    /// Example:
+    ///     >>> session.tail(0)              # Ctrl-C to stop
+    ///     >>> session.tail(0, duration_ms=5000)
+    #[pyo3(signature = (channel, duration_ms=None))]
+    fn tail(&self, py: Python<'_>, channel: usize, duration_ms: Option<u64>) -> PyResult<usize> {
+        let deadline = duration_ms.map(|ms| Instant::now() + Duration::from_millis(ms));
+        // Looked up on every write, so redirection during the call is honoured
+        let write_stdout = |bytes: &[u8]| -> PyResult<()> {
+            let stdout = py.import("sys")?.getattr("stdout")?;
+            stdout.call_method1("write", (String::from_utf8_lossy(bytes),))?;
+            stdout.call_method0("flush")?;
+            Ok(())
+        };
+
+        let mut total = 0;
+        let mut pending = Vec::new();
+        while deadline.map_or(true, |d| Instant::now() < d) {
+            match py.check_signals() {
+                Err(e) if e.is_instance_of::<pyo3::exceptions::PyKeyboardInterrupt>(py) => break,
+                result => result?,
+            }
+            let data = py.allow_threads(|| self.read_channel(channel))?;
+            if data.is_empty() {
+                let poll_interval = self.rtt_tuning.lock().unwrap().poll_interval;
+                py.allow_threads(|| std::thread::sleep(poll_interval));
+                continue;
+            }
+            total += data.len();
+            pending.extend_from_slice(&data);
+
+            if let Some(last_newline) = pending.iter().rposition(|&b| b == b'\n') {
+                write_stdout(&pending[..=last_newline])?;
+                pending.drain(..=last_newline);
+            }
+        }
+
+        if !pending.is_empty() {
+            write_stdout(&pending)?;
+        }
+        Ok(total)
+    }
+
+    /// Resume a halted core and capture an up channel from its first output.
+    ///
+    /// For zero-loss boot logs: attach with `connect_mode="halt_after_reset"` (or
+    /// `reset(halt=True)`), then call this. The core is resumed and the channel is
+    /// drained until `duration_ms` has passed. RTT buffers hold data until it is
+    /// read, so nothing written before the first read is lost unless the channel
+    /// buffer fills up first (size it for the boot banner).
+    ///
+    /// While the core sits at the reset vector the firmware hasn't initialized the
+    /// control block yet, so RTT can't be armed beforehand. If RTT isn't started,
+    /// this waits for the block right after resuming (as wait_for_rtt() does, at
+    /// elf_path / block_address or by RAM scan) and then drains from the start.
+    ///
+    /// Args:
+    ///     channel: RTT up channel index (0-based)
+    ///     duration_ms: How long to capture after resuming, including any wait for
+    ///         the control block
+    ///     elf_path: Optional ELF path to read the _SEGGER_RTT address from
+    ///     block_address: Optional control block address (takes precedence)
+    ///
+    /// Returns:
+    ///     bytes: Everything read from the channel
+    ///
+    /// Raises:
+    ///     TimeoutError: If RTT wasn't started and the control block isn't
+    ///         initialized within duration_ms
+    ///     RuntimeError: If not attached, resuming fails, or a read fails
+    ///     PermissionError: If the session is read-only
+    ///
+    /// Example:
+    ///     >>> session.attach(connect_mode="halt_after_reset")
+    ///     >>> boot_log = session.run_and_capture(0, 2000, elf_path="build/zephyr/zephyr.elf")
+    #[pyo3(signature = (channel, duration_ms, elf_path=None, block_address=None))]
+    fn run_and_capture(
+        &self,
+        py: Python<'_>,
+        channel: usize,
+        duration_ms: u64,
+        elf_path: Option<String>,
+        block_address: Option<u64>,
+    ) -> PyResult<Py<PyBytes>> {
+        self.check_writable("run_and_capture()")?;
+        self.with_core(|core| {
+            let halted = core.core_halted().map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to read core status: {}", e))
+            })?;
+            if halted {
+                core.run().map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to resume core: {}", e))
+                })?;
+            }
+            Ok(())
+        })?;
+        let deadline = Instant::now() + Duration::from_millis(duration_ms);
+
+        if !self.is_rtt_active() {
+            self.wait_for_rtt(py, elf_path, block_address, duration_ms)?;
+        }
+
+        let mut captured = Vec::new();
+        while Instant::now() < deadline {
+            py.check_signals()?;
+            let data = py.allow_threads(|| self.read_channel(channel))?;
+            if data.is_empty() {
+                let poll_interval = self.rtt_tuning.lock().unwrap().poll_interval;
+                py.allow_threads(|| std::thread::sleep(poll_interval));
+                continue;
+            }
+            captured.extend_from_slice(&data);
+        }
+        // Pick up whatever arrived during the last poll interval
+        captured.extend_from_slice(&py.allow_threads(|| self.read_channel(channel))?);
+
+        Ok(PyBytes::new(py, &captured).into())
+    }
+
+    /// Perform several RTT writes and reads as one uninterrupted transaction.
+    ///
+    /// For protocols split across channels (e.g. a control command on one down
+    /// channel and its payload on another), all writes and then all reads are done
+    /// in order under a single lock and core attach, so no other call can slip in
+    /// between them.
+    ///
+    /// Args:
+    ///     writes: List of (down_channel, data) pairs, written in order
+    ///     reads: List of (up_channel, max_bytes) pairs, read in order after the writes
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         written: list[int] bytes accepted for each write (may be short if
+    ///             a down buffer is full)
+    ///         read: list[bytes] data read for each read (may be empty)
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached, RTT not started, or a transfer fails
+    ///     ValueError: If a channel doesn't exist
+    ///
+    /// Example:
+    ///     >>> result = session.rtt_multi_transaction(
+    ///     ...     writes=[(1, b"\x01START"), (2, payload)],
+    ///     ...     reads=[(1, 64), (2, 4096)],
+    ///     ... )
+    ///     >>> status, data = result["read"]
+    fn rtt_multi_transaction<'py>(
+        &self,
+        py: Python<'py>,
+        writes: Vec<(usize, Vec<u8>)>,
+        reads: Vec<(usize, usize)>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        if !writes.is_empty() {
+            self.check_writable("rtt_multi_transaction() writes")?;
+        }
+        let paused = self.rtt_paused.load(Ordering::Relaxed);
+
+        let (written, read) = self.with_rtt(|core, rtt| {
+            let block = u64::from(rtt.ptr());
+            let mut written = Vec::with_capacity(writes.len());
+            for (channel, data) in &writes {
+                let down_channel = rtt
+                    .down_channels()
+                    .get_mut(*channel)
+                    .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("Channel {} not found", channel)))?;
+                written.push(down_channel.write(core, data).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!("RTT write failed: {}", e))
+                })?);
+            }
+
+            let mut read = Vec::with_capacity(reads.len());
+            for &(channel, max_bytes) in &reads {
+                let up_channel = rtt
+                    .up_channels()
+                    .get_mut(channel)
+                    .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("Channel {} not found", channel)))?;
+                if paused {
+                    read.push((channel, Vec::new(), None));
+                    continue;
+                }
+                let mut buffer = vec![0u8; max_bytes];
+                let count = up_channel.read(core, &mut buffer).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!("RTT read failed: {}", e))
+                })?;
+                buffer.truncate(count);
+                let buffer_size = up_channel.buffer_size();
+                let number = up_channel.number();
+                let fill = rtt_read_fill(core, self.retry_policy, block, number, count, max_bytes, buffer_size);
+                read.push((channel, buffer, Some((fill, buffer_size))));
+            }
+
+            Ok((written, read))
+        })?;
+
+        let read_bytes = PyList::empty(py);
+        for (channel, data, sizes) in &read {
+            if let Some((fill, buffer_size)) = sizes {
+                self.record_read(*channel, data, *fill, *buffer_size)?;
+            }
+            read_bytes.append(PyBytes::new(py, data))?;
+        }
+
+        let dict = PyDict::new(py);
+        dict.set_item("written", written)?;
+        dict.set_item("read", read_bytes)?;
+        Ok(dict)
+    }
+
+    /// Report per-channel RTT buffer sizes against observed throughput.
+    ///
+    /// Flags channels whose target-side buffer is likely too small: if reads often
+    /// find the buffer completely full, the firmware is producing data faster than
+    /// the buffer can hold between polls, so it is dropping (or blocking on) data.
+    /// When a read is capped by the chunk size (buffers larger than
+    /// read_chunk_size), the fill level is taken from the descriptor instead.
+    /// The fix is a larger buffer in the firmware (e.g.
+    /// CONFIG_SEGGER_RTT_BUFFER_SIZE_UP), not host-side changes. Statistics cover
+    /// reads since the last `start_rtt()`.
+    ///
+    /// Returns:
+    ///     list[dict], one per up channel, with keys:
+    ///         channel: int up channel index
+    ///         name: str | None channel name from the control block
+    ///         buffer_size: int target-side buffer size in bytes
+    ///         reads: int number of reads
+    ///         bytes_read: int total bytes read
+    ///         full_reads: int reads that found the buffer full
+    ///         throughput_bps: float average bytes/second since the first read
+    ///         buffer_too_small: bool True if more than 5% of reads (and at least 3)
+    ///             found the buffer full
+    ///
+    /// Raises:
+    ///     RuntimeError: If RTT is not started
+    ///
+    /// Example:
+    ///     >>> for ch in session.check_rtt_health():
+    ///     ...     if ch["buffer_too_small"]:
+    ///     ...         print(f"Channel {ch['channel']}: increase buffer ({ch['buffer_size']} B)")
+    fn check_rtt_health<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        let channels: Vec<(Option<String>, usize)> = self
+            .rtt
+            .lock()
+            .unwrap()
+            .as_mut()
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("RTT not started. Call start_rtt() first."))?
+            .up_channels()
+            .iter()
+            .map(|ch| (ch.name().map(str::to_string), ch.buffer_size()))
+            .collect();
+
+        let stats = self.rtt_stats.lock().unwrap();
+        let report = PyList::empty(py);
+        for (index, (name, buffer_size)) in channels.into_iter().enumerate() {
+            let (reads, bytes, full_reads, elapsed) = match stats.get(&index) {
+                Some(s) => (s.reads, s.bytes, s.full_reads, s.first_read.elapsed().as_secs_f64()),
+                None => (0, 0, 0, 0.0),
+            };
+            let throughput = if elapsed > 0.0 { bytes as f64 / elapsed } else { 0.0 };
+            let too_small = full_reads >= 3 && full_reads * 20 > reads;
+
+            let entry = PyDict::new(py);
+            entry.set_item("channel", index)?;
+            entry.set_item("name", name)?;
+            entry.set_item("buffer_size", buffer_size)?;
+            entry.set_item("reads", reads)?;
+            entry.set_item("bytes_read", bytes)?;
+            entry.set_item("full_reads", full_reads)?;
+            entry.set_item("throughput_bps", throughput)?;
+            entry.set_item("buffer_too_small", too_small)?;
+            report.append(entry)?;
+        }
+        Ok(report)
+    }
+
+    /// Read the operating mode of every RTT up and down channel in one call.
+    ///
+    /// The mode lives in the low bits of each buffer descriptor's flags word and
+    /// is set by the firmware (e.g. SEGGER_RTT_ConfigUpBuffer). All descriptors
+    /// sit back to back in the control block, so they are fetched with a single
+    /// memory read instead of one round trip per channel. Useful for diagnostics
+    /// views that show the whole RTT configuration at once.
+    ///
+    /// Returns:
+    ///     dict with keys "up" and "down", each a list of dicts with keys:
+    ///         channel: int channel index
+    ///         name: str | None channel name from the control block
+    ///         buffer_size: int target-side buffer size in bytes
+    ///         mode: str "no_block_skip", "no_block_trim", "block_if_full"
+    ///             (or "invalid" for the reserved encoding)
+    ///         flags: int raw flags word
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached, RTT is not started, or the read fails
+    ///
+    /// Example:
+    ///     >>> modes = session.all_channel_modes()
+    ///     >>> for ch in modes["up"]:
+    ///     ...     print(ch["channel"], ch["name"], ch["mode"])
+    fn all_channel_modes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let (up, down, descriptors) = self.with_rtt(|core, rtt| {
+            // number() is the channel's descriptor index in the control block
+            let up: Vec<(usize, Option<String>, usize)> = rtt
+                .up_channels()
+                .iter()
+                .map(|ch| (ch.number(), ch.name().map(str::to_string), ch.buffer_size()))
+                .collect();
+            let down: Vec<(usize, Option<String>, usize)> = rtt
+                .down_channels()
+                .iter()
+                .map(|ch| (ch.number(), ch.name().map(str::to_string), ch.buffer_size()))
+                .collect();
+
+            let descriptors = self.rtt_descriptors(core, rtt)?;
+            Ok((up, down, descriptors))
+        })?;
+
+        let dict = PyDict::new(py);
+        for (key, channels, is_up) in [("up", &up, true), ("down", &down, false)] {
+            let list = PyList::empty(py);
+            for (index, (number, name, buffer_size)) in channels.iter().enumerate() {
+                let descriptor = if is_up { descriptors.up(*number)? } else { descriptors.down(*number)? };
+                // Descriptor words: name, buffer, size, write offset, read offset, flags
+                let flags = descriptor[5];
+                let entry = PyDict::new(py);
+                entry.set_item("channel", index)?;
+                entry.set_item("name", name)?;
+                entry.set_item("buffer_size", buffer_size)?;
+                entry.set_item("mode", rtt_mode_name(flags))?;
+                entry.set_item("flags", flags)?;
+                list.append(entry)?;
+            }
+            dict.set_item(key, list)?;
+        }
+        Ok(dict)
+    }
+
+    /// Report how many bytes are waiting in each up channel, without reading them.
+    ///
+    /// Computed from each buffer's write and read offsets in the control block
+    /// (fetched in one memory read), so nothing is consumed and the read pointers
+    /// are untouched. Meant for "data waiting" indicators and for deciding which
+    /// channels to read. Works while RTT is paused, since it only inspects the
+    /// control block.
+    ///
+    /// Returns:
+    ///     dict[int, int]: up channel index -> bytes pending in the target buffer
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached, RTT is not started, or the read fails
+    ///     RttError: If the control block no longer matches the attached channels
+    ///
+    /// Example:
+    ///     >>> pending = session.available_bytes()
+    ///     >>> busy = [ch for ch, n in pending.items() if n > 0]
+    fn available_bytes(&self) -> PyResult<HashMap<usize, usize>> {
+        let (numbers, descriptors) = self.with_rtt(|core, rtt| {
+            let numbers: Vec<usize> = rtt.up_channels().iter().map(|ch| ch.number()).collect();
+            let descriptors = self.rtt_descriptors(core, rtt)?;
+            Ok((numbers, descriptors))
+        })?;
+
+        let mut available = HashMap::new();
+        for (index, number) in numbers.into_iter().enumerate() {
+            available.insert(index, rtt_descriptor_pending(descriptors.up(number)?));
+        }
+        Ok(available)
+    }
+
+    /// Pick RTT read chunk size and poll interval to suit the attached probe.
+    ///
+    /// Probes differ a lot in what polling pattern streams best: J-Link prefers
+    /// large, infrequent reads, HID CMSIS-DAP smaller ones polled more often. This
+    /// looks at the probe selected by attach() and applies defaults for its type
+    /// (unknown probes keep the generic 4 KB / 1 ms). The chunk size applies to
+    /// every RTT read; the poll interval is the idle pause in the streaming loops
+    /// (monitor(), tail(), rtt_write_and_verify_echo()).
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         probe_kind: str "jlink", "stlink", "cmsis_dap", "esp_usb_jtag",
+    ///             "ftdi" or "generic"
+    ///         read_chunk_size: int bytes requested per RTT read
+    ///         poll_interval_ms: float idle pause between polls
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached
+    ///
+    /// Example:
+    ///     >>> session.attach()
+    ///     >>> print(session.tune_for_probe())
+    fn tune_for_probe<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let tuning = self
+            .selected_probe
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(RttTuning::for_probe)
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Not attached"))?;
+        *self.rtt_tuning.lock().unwrap() = tuning;
+
+        let dict = PyDict::new(py);
+        dict.set_item("probe_kind", tuning.probe_kind)?;
+        dict.set_item("read_chunk_size", tuning.read_chunk_size)?;
+        dict.set_item("poll_interval_ms", tuning.poll_interval.as_secs_f64() * 1000.0)?;
+        Ok(dict)
+    }
+
+    /// Check that the RTT control block is still the one start_rtt() attached to.
+    ///
+    /// If the firmware resets itself (watchdog, software reset) while streaming, the
+    /// control block is cleared and rebuilt, and reads can silently break. This
+    /// re-reads the block's ID and up-channel count at the attached address. A
+    /// mismatch sets `rtt_desync_detected`, which read failures also set; once set,
+    /// this keeps returning False until a resync or start_rtt().
+    ///
+    /// Args:
+    ///     resync: If the block has changed but a valid one is back at the same
+    ///         address, reattach to it (default False). Clears `rtt_desync_detected`,
+    ///         resets channel statistics and partial frames as start_rtt() does,
+    ///         and drops retained history data (set_history() sizes are kept).
+    ///
+    /// Returns:
+    ///     bool: True if RTT is valid (after resyncing, if requested)
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached, RTT is not started, or the read fails
+    ///
+    /// Example:
+    ///     >>> if not session.check_rtt_valid(resync=True):
+    ///     ...     session.wait_for_rtt(elf_path="build/zephyr/zephyr.elf")
+    #[pyo3(signature = (resync=false))]
+    fn check_rtt_valid(&self, resync: bool) -> PyResult<bool> {
+        let (valid, resynced) = self.with_rtt(|core, rtt| {
+            // A block that looks intact now may still have been rebuilt since a failed
+            // read flagged it, so only a clean check with no prior desync counts as valid
+            let block = u64::from(rtt.ptr());
+            let intact = rtt_block_intact(core, self.retry_policy, block, rtt.up_channels().len())?;
+            if intact && !self.rtt_desync.load(Ordering::Relaxed) {
+                return Ok((true, false));
+            }
+            self.rtt_desync.store(true, Ordering::Relaxed);
+            if !resync {
+                return Ok((false, false));
+            }
+
+            // Reattach only once the firmware has rebuilt the block
+            match Rtt::attach_at(core, block) {
+                Ok(new_rtt) => {
+                    *rtt = new_rtt;
+                    self.rtt_desync.store(false, Ordering::Relaxed);
+                    Ok((true, true))
+                }
+                Err(_) => Ok((false, false)),
+            }
+        })?;
+
+        // What was read before the target reset belongs to the old firmware run:
+        // start counting and framing afresh, and keep history settings but not data
+        if resynced {
+            self.reset_channel_state();
+            for history in self.history.lock().unwrap().values_mut() {
+                history.data.clear();
+            }
+        }
+        Ok(valid)
+    }
+
+    /// Write to a down channel and check that the target echoes it on an up channel.
+    ///
+    /// Loopback test for the RTT command path: writes all of `data` (retrying
+    /// partial writes while the down buffer drains), then reads `up_channel` until
+    /// as many bytes as were sent have arrived, and compares them. Doing both
+    /// halves in Rust avoids the race of the echo arriving between Python calls.
+    /// The echoed bytes are consumed from the up channel.
+    ///
+    /// Args:
+    ///     down_channel: RTT down channel index to write to
+    ///     up_channel: RTT up channel index the firmware echoes on
+    ///     data: Bytes to send
+    ///     timeout_ms: Overall time limit for writing and receiving the echo
+    ///
+    /// Returns:
+    ///     bool: True if the first len(data) bytes received equal data; False on
+    ///     mismatch or if the echo didn't arrive in time
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached, RTT not started, or a read/write fails
+    ///     ValueError: If a channel doesn't exist
+    ///
+    /// Example:
+    ///     >>> assert session.rtt_write_and_verify_echo(0, 0, b"ping\n", timeout_ms=500)
+    fn rtt_write_and_verify_echo(
+        &self,
+        py: Python<'_>,
+        down_channel: usize,
+        up_channel: usize,
+        data: &[u8],
+        timeout_ms: u64,
+    ) -> PyResult<bool> {
+        let deadline = Instant::now() + Duration::from_millis(timeout_ms);
+
+        let mut sent = 0;
+        while sent < data.len() {
+            if Instant::now() >= deadline {
+                return Ok(false);
+            }
+            sent += self.rtt_write(py, down_channel, &data[sent..], false)?;
+            if sent < data.len() {
+                let poll_interval = self.rtt_tuning.lock().unwrap().poll_interval;
+                py.allow_threads(|| std::thread::sleep(poll_interval));
+            }
+        }
+
+        let mut received = Vec::with_capacity(data.len());
+        while received.len() < data.len() {
+            if Instant::now() >= deadline {
+                return Ok(false);
+            }
+            let chunk = py.allow_threads(|| self.read_channel(up_channel))?;
+            if chunk.is_empty() {
+                let poll_interval = self.rtt_tuning.lock().unwrap().poll_interval;
+                py.allow_threads(|| std::thread::sleep(poll_interval));
+            }
+            received.extend_from_slice(&chunk);
+        }
+
+        Ok(received[..data.len()] == *data)
+    }
+
+    /// Start recording all RTT data read to a timestamped binary log file.
+    ///
+    /// Every non-empty read on any up channel (through any read method) is
+    /// appended as a framed record, giving a durable, replayable capture for
+    /// offline analysis. Replaces any capture log already active.
+    ///
+    /// File format (all integers little-endian):
+    ///
+    ///     header:  8 bytes  magic "EABRTT01"
+    ///     record:  1 byte   up channel index
+    ///              8 bytes  host timestamp, microseconds since the Unix epoch (u64)
+    ///              4 bytes  payload length N (u32)
+    ///              N bytes  payload, exactly as read from the channel
+    ///
+    /// Records follow each other with no padding until end of file.
+    ///
+    /// For long soak tests, set `max_file_bytes` to rotate like a log rotator:
+    /// when the next record would push `path` past the limit, it is renamed to
+    /// `path.1` (older files shift to `path.2`, ...; the oldest beyond
+    /// `max_files` is deleted) and a new `path` is started. Every file starts
+    /// with its own header and holds whole records, so each can be parsed alone.
+    ///
+    /// Args:
+    ///     path: File to write (truncated if it exists)
+    ///     max_file_bytes: Optional size limit per file; None never rotates
+    ///     max_files: Total files kept when rotating, including the current one
+    ///         (default 5), which bounds disk use to about
+    ///         max_file_bytes * max_files
+    ///
+    /// Raises:
+    ///     IOError: If the file can't be created
+    ///     ValueError: If max_files is 0
+    ///
+    /// Example:
+    ///     >>> session.start_capture_log("capture.eabrtt")
+    ///     >>> session.start_capture_log("soak.eabrtt", max_file_bytes=64 << 20, max_files=10)
+    #[pyo3(signature = (path, max_file_bytes=None, max_files=5))]
+    fn start_capture_log(&self, path: &str, max_file_bytes: Option<u64>, max_files: usize) -> PyResult<()> {
+        if max_files == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err("max_files must be at least 1"));
+        }
+        let log = CaptureLog::create(path, max_file_bytes, max_files).map_err(|e| {
+            pyo3::exceptions::PyIOError::new_err(format!(
+                "Failed to create capture log '{}': {}",
+                path, e
+            ))
+        })?;
+        *self.capture_log.lock().unwrap() = Some(log);
+        Ok(())
+    }
+
+    /// Stop recording and flush the capture log (no-op if none is active).
+    ///
+    /// Raises:
+    ///     IOError: If flushing the file fails
+    ///
+    /// Example:
+    ///     >>> session.stop_capture_log()
+    fn stop_capture_log(&self) -> PyResult<()> {
+        if let Some(mut log) = self.capture_log.lock().unwrap().take() {
+            log.writer.flush().map_err(|e| {
+                pyo3::exceptions::PyIOError::new_err(format!("Failed to flush capture log: {}", e))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Stop draining RTT up channels without tearing RTT down.
+    ///
+    /// While paused, every read method returns empty data and doesn't touch the
+    /// target, so the firmware's up buffers keep holding data (up to their size;
+    /// after that the firmware's buffer mode decides whether it blocks or drops).
+    /// RTT stays attached and writes still work. Use `resume_rtt()` to continue.
+    ///
+    /// Example:
+    ///     >>> session.pause_rtt()   # user paused the log view
+    fn pause_rtt(&self) {
+        self.rtt_paused.store(true, Ordering::Relaxed);
+    }
+
+    /// Resume draining RTT up channels after `pause_rtt()`.
+    ///
+    /// The next reads return the data buffered on the target while paused.
+    ///
+    /// Example:
+    ///     >>> session.resume_rtt()
+    fn resume_rtt(&self) {
+        self.rtt_paused.store(false, Ordering::Relaxed);
+    }
+
+    /// Write raw bytes to an RTT down (host→target) channel.
+    ///
+    /// Args:
+    ///     channel: RTT down channel index (0-based)
+    ///     data: Bytes to write
+    ///     require_full: If True, write nothing and raise RttError unless the
+    ///         whole payload fits in the down buffer right now, instead of
+    ///         returning a short count that is easy to ignore. For commands where
+    ///         a truncated write is a bug (default False)
+    ///
+    /// Returns:
+    ///     int: Number of bytes actually written (may be less than len(data) if
+    ///         buffer full; always len(data) with require_full=True)
+    ///
+    /// Raises:
+    ///     RttError: If require_full is set and the payload doesn't fit. The
+    ///         exception has `accepted` (free space) and `requested` attributes.
+    ///         Also raised if the down buffer descriptor no longer makes sense
+    ///     RuntimeError: If RTT not started or channel doesn't exist
+    ///
+    /// Example:
+    ///     >>> written = session.rtt_write(channel=0, data=b"command")
+    ///     >>> print(f"Wrote {written} bytes")
+    ///     >>> session.rtt_write(channel=0, data=b"reboot\n", require_full=True)
+    #[pyo3(signature = (channel, data, require_full=false))]
+    fn rtt_write(&self, py: Python<'_>, channel: usize, data: &[u8], require_full: bool) -> PyResult<usize> {
+        self.check_writable("rtt_write()")?;
+        self.with_rtt(|core, rtt| {
+            let descriptors = if require_full { Some(self.rtt_descriptors(core, rtt)?) } else { None };
+            let down_channel = rtt
+                .down_channels()
+                .get_mut(channel)
+                .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("Channel {} not found", channel)))?;
+
+            if let Some(descriptors) = descriptors {
+                // The firmware only ever frees space (by reading), so a payload that
+                // fits now still fits when it is written below
+                // Descriptor words: name, buffer, size, write offset, read offset, flags
+                let descriptor = descriptors.down(down_channel.number())?;
+                let (size, write, read) = (descriptor[2] as usize, descriptor[3] as usize, descriptor[4] as usize);
+                if write >= size || read >= size {
+                    self.rtt_desync.store(true, Ordering::Relaxed);
+                    return Err(RttError::new_err(format!(
+                        "Down channel {} descriptor is corrupt (size {}, write {}, read {}); \
+                         call check_rtt_valid(resync=True) or start_rtt() again.",
+                        channel, size, write, read
+                    )));
+                }
+                // One slot always stays empty to tell a full buffer from an empty one
+                let free = if read > write { read - write - 1 } else { (size + read).saturating_sub(write + 1) };
+                if free < data.len() {
+                    let err = RttError::new_err(format!(
+                        "Down channel {} can accept {} of {} bytes",
+                        channel,
+                        free,
+                        data.len()
+                    ));
+                    err.value(py).setattr("accepted", free)?;
+                    err.value(py).setattr("requested", data.len())?;
+                    return Err(err);
+                }
+            }
+
+            down_channel.write(core, data).map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!("RTT write failed: {}", e))
+            })
+        })
+    }
+
+    /// Reset the target chip.
+    ///
+    /// Args:
+    ///     halt: If True, halt the core after reset (for debugging)
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached
+    ///
+    /// Example:
+    ///     >>> session.reset(halt=False)
+    #[pyo3(signature = (halt=false))]
+    fn reset(&self, halt: bool) -> PyResult<()> {
+        self.check_writable("reset()")?;
+        // No lockup check: reset is how a locked-up core is recovered
+        self.access_core(false, |core| {
+            core.reset().map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!("Reset failed: {}", e))
+            })?;
+
+            if halt {
+                core.halt(std::time::Duration::from_millis(100)).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!("Halt failed: {}", e))
+                })?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Reset the target and run until it reaches a symbol (by default `main`).
+    ///
+    /// The usual first step of boot debugging. The core is reset and held at the
+    /// reset vector, a hardware breakpoint is set on the symbol, and only then is
+    /// the core released, so fast startup code can't get past the symbol before
+    /// the breakpoint is in place. Returns with the core halted at the symbol; the
+    /// breakpoint is removed again either way.
+    ///
+    /// Args:
+    ///     elf_path: Path to the ELF file the running firmware was built from
+    ///     symbol: Function to stop at (default "main")
+    ///     timeout_ms: How long to wait for the breakpoint to hit (default 5000)
+    ///
+    /// Returns:
+    ///     int: Address the core halted at
+    ///
+    /// Raises:
+    ///     ValueError: If the symbol is not found in the ELF
+    ///     PermissionError: On a read-only session
+    ///     TimeoutError: If the breakpoint isn't hit within timeout_ms (the core is
+    ///         left running)
+    ///     RuntimeError: If not attached, no hardware breakpoint is free, or a
+    ///         debug operation fails
+    ///
+    /// Example:
+    ///     >>> session.break_at_symbol("build/zephyr/zephyr.elf")
+    ///     >>> session.read_symbol_u32("build/zephyr/zephyr.elf", "boot_count")
+    #[pyo3(signature = (elf_path, symbol="main", timeout_ms=5000))]
+    fn break_at_symbol(&self, py: Python<'_>, elf_path: &str, symbol: &str, timeout_ms: u64) -> PyResult<u64> {
+        self.check_writable("break_at_symbol()")?;
+        // Thumb function symbols have bit 0 set; the breakpoint needs the real address
+        let address = require_symbol(elf_path, symbol)? & !1;
+
+        py.allow_threads(|| {
+            self.with_core(|core| {
+                core.reset_and_halt(Duration::from_millis(100)).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!("Reset failed: {}", e))
+                })?;
+                core.set_hw_breakpoint(address).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Failed to set breakpoint at '{}' (0x{:08x}): {}",
+                        symbol, address, e
+                    ))
+                })?;
+
+                let result = core
+                    .run()
+                    .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to run core: {}", e)))
+                    .and_then(|_| {
+                        core.wait_for_core_halted(Duration::from_millis(timeout_ms)).map_err(|_| {
+                            pyo3::exceptions::PyTimeoutError::new_err(format!(
+                                "'{}' not reached within {} ms",
+                                symbol, timeout_ms
+                            ))
+                        })
+                    });
+
+                // Breakpoint units are scarce; never leave this one behind
+                let cleared = core.clear_hw_breakpoint(address).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to clear breakpoint: {}", e))
+                });
+                result?;
+                cleared?;
+
+                let pc_register = core.program_counter();
+                core.read_core_reg(pc_register).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to read PC: {}", e))
+                })
+            })
+        })
+    }
+
+    /// Set hardware breakpoints on several symbols at once, all or nothing.
+    ///
+    /// Every symbol is resolved first and the number of free breakpoint units is
+    /// checked against the request before anything is touched. If setting one of
+    /// the breakpoints still fails, the ones already set by this call are cleared
+    /// again, so the target is never left half-instrumented. Breakpoints that are
+    /// already set at one of the addresses are reused and don't count against the
+    /// budget. The core is not halted or resumed.
+    ///
+    /// Args:
+    ///     elf_path: Path to the ELF file the running firmware was built from
+    ///     symbols: Function names to break on
+    ///
+    /// Returns:
+    ///     dict[str, int]: symbol -> breakpoint address
+    ///
+    /// Raises:
+    ///     ValueError: If any symbol is not found in the ELF
+    ///     PermissionError: On a read-only session
+    ///     RuntimeError: If not attached, there are not enough free breakpoint
+    ///         units (the message gives needed/free counts), or setting a
+    ///         breakpoint fails (after rolling back)
+    ///
+    /// Example:
+    ///     >>> session.set_breakpoints_at_symbols("zephyr.elf", ["k_panic", "z_fatal_error", "assert_post_action"])
+    fn set_breakpoints_at_symbols(&self, elf_path: &str, symbols: Vec<String>) -> PyResult<HashMap<String, u64>> {
+        self.check_writable("set_breakpoints_at_symbols()")?;
+        let names: Vec<&str> = symbols.iter().map(String::as_str).collect();
+        // Thumb function symbols have bit 0 set; the breakpoint needs the real address
+        let addresses: HashMap<String, u64> = find_symbols(elf_path, &names)?
+            .into_iter()
+            .map(|(name, address)| (name, address & !1))
+            .collect();
+
+        self.with_core(|core| {
+            let units = core.hw_breakpoints().map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to read breakpoint units: {}", e))
+            })?;
+            let free = units.iter().filter(|unit| unit.is_none()).count();
+
+            let mut needed: Vec<u64> = addresses
+                .values()
+                .copied()
+                .filter(|address| !units.contains(&Some(*address)))
+                .collect();
+            needed.sort_unstable();
+            needed.dedup();
+            if needed.len() > free {
+                return Err(pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "Not enough hardware breakpoints: {} needed, {} free of {}",
+                    needed.len(),
+                    free,
+                    units.len()
+                )));
+            }
+
+            for (index, address) in needed.iter().enumerate() {
+                if let Err(e) = core.set_hw_breakpoint(*address) {
+                    for set in &needed[..index] {
+                        let _ = core.clear_hw_breakpoint(*set);
+                    }
+                    return Err(pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Failed to set breakpoint at 0x{:08x} (rolled back): {}",
+                        address, e
+                    )));
+                }
+            }
+            Ok(())
+        })?;
+
+        Ok(addresses)
+    }
+
+    /// Poll a memory location on a background thread and call back when it changes.
+    ///
+    /// A software watchpoint for running targets: hardware watchpoints halt the
+    /// core, this only reads. The value is read every `interval_ms` with the GIL
+    /// released; the GIL is taken only to call `callback(value)`, and only when the
+    /// value differs from the previous read (not for the first read). Changes that
+    /// revert within one interval are missed. An exception raised by the callback
+    /// is reported via sys.unraisablehook and polling continues. A failed read
+    /// (e.g. after detach()) ends polling; the handle's `error` says why.
+    ///
+    /// Call `stop()` before the interpreter exits: it joins the thread. Dropping the
+    /// handle only signals the thread (joining there could deadlock on the GIL), and
+    /// a watch still running at shutdown stops without further callbacks.
+    ///
+    /// Args:
+    ///     address: Address to watch (aligned to the width)
+    ///     width: Access width in bits: 8, 16 or 32
+    ///     interval_ms: Poll interval in milliseconds (at least 1)
+    ///     callback: Callable taking the new value (int)
+    ///
+    /// Returns:
+    ///     MemoryWatch: handle with stop(), is_running, value and error
+    ///
+    /// Raises:
+    ///     ValueError: If width is not 8, 16 or 32, the address is misaligned or
+    ///         interval_ms is 0
+    ///
+    /// Example:
+    ///     >>> watch = session.watch_memory(0x20000100, 32, 50, lambda v: print(hex(v)))
+    ///     >>> time.sleep(10)
+    ///     >>> watch.stop()
+    #[pyo3(signature = (address, width, interval_ms, callback))]
+    fn watch_memory(
+        slf: &Bound<'_, Self>,
+        address: u64,
+        width: u32,
+        interval_ms: u64,
+        callback: PyObject,
+    ) -> PyResult<MemoryWatch> {
+        if !matches!(width, 8 | 16 | 32) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "width must be 8, 16 or 32, got {}",
+                width
+            )));
+        }
+        check_alignment(address, width)?;
+        // A zero interval would spin on the session lock and starve every other caller
+        if interval_ms == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err("interval_ms must be at least 1"));
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let state = Arc::new(Mutex::new((None, None)));
+        let session = slf.clone().unbind();
+        let interval = Duration::from_millis(interval_ms);
+
+        let thread = {
+            let stop = stop.clone();
+            let state = state.clone();
+            std::thread::spawn(move || {
+                // The session class is frozen, so this needs no GIL; the GIL is
+                // only taken for the callback
+                let session: &ProbeRsSession = session.get();
+                let mut last: Option<u32> = None;
+                while !stop.load(Ordering::Relaxed) {
+                    let result = session.with_core(|core| {
+                        retry_transient(self.retry_policy, || match width {
+                            8 => core.read_word_8(address).map(u32::from),
+                            16 => core.read_word_16(address).map(u32::from),
+                            _ => core.read_word_32(address),
+                        })
+                        .map_err(|e| {
+                            pyo3::exceptions::PyRuntimeError::new_err(format!(
+                                "Memory read at 0x{:08x} failed: {}",
+                                address, e
+                            ))
+                        })
+                    });
+
+                    match result {
+                        Ok(value) => {
+                            if last.is_some_and(|previous| previous != value) {
+                                // Past interpreter shutdown there is no GIL to take;
+                                // just stop
+                                if stop.load(Ordering::Relaxed) || unsafe { ffi::Py_IsInitialized() } == 0 {
+                                    break;
+                                }
+                                Python::with_gil(|py| {
+                                    if let Err(e) = callback.call1(py, (value,)) {
+                                        e.write_unraisable(py, Some(callback.bind(py)));
+                                    }
+                                });
+                            }
+                            last = Some(value);
+                            state.lock().unwrap().0 = Some(value);
+                        }
+                        Err(e) => {
+                            state.lock().unwrap().1 = Some(e.to_string());
+                            break;
+                        }
+                    }
+                    std::thread::sleep(interval);
+                }
+            })
+        };
+
+        Ok(MemoryWatch {
+            stop,
+            thread: Mutex::new(Some(thread)),
+            state,
+        })
+    }
+
+    /// Read 16-bit half-words from target memory.
+    ///
+    /// For half-word peripheral registers and packed 16-bit sample buffers.
+    ///
+    /// Args:
+    ///     address: Start address (must be 2-byte aligned)
+    ///     count: Number of half-words to read (default 1)
+    ///
+    /// Returns:
+    ///     list[int]: Half-words read, in target byte order
+    ///
+    /// Raises:
+    ///     ValueError: If the address is not 2-byte aligned
+    ///     RuntimeError: If not attached or the memory read fails
+    ///
+    /// Example:
+    ///     >>> samples = session.read_mem16(0x20001000, count=512)
+    #[pyo3(signature = (address, count=1))]
+    fn read_mem16(&self, address: u64, count: usize) -> PyResult<Vec<u16>> {
+        check_alignment(address, 16)?;
+        self.with_core(|core| {
+            let mut values = vec![0u16; count];
+            retry_transient(self.retry_policy, || core.read_16(address, &mut values)).map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "Memory read at 0x{:08x} failed: {}",
+                    address, e
+                ))
+            })?;
+            Ok(values)
+        })
+    }
+
+    /// Write 16-bit half-words to target memory.
+    ///
+    /// Args:
+    ///     address: Start address (must be 2-byte aligned)
+    ///     values: Half-words to write
+    ///     byte_swap: Reverse the byte order of each value before writing, for
+    ///         data captured on a system of the other endianness (default False)
+    ///
+    /// Raises:
+    ///     ValueError: If the address is not 2-byte aligned
+    ///     RuntimeError: If not attached or the memory write fails
+    ///
+    /// Example:
+    ///     >>> session.write_mem16(0x20001000, [0x1234, 0xABCD])
+    ///     >>> session.write_mem16(0x20001000, big_endian_samples, byte_swap=True)
+    #[pyo3(signature = (address, values, byte_swap=false))]
+    fn write_mem16(&self, address: u64, mut values: Vec<u16>, byte_swap: bool) -> PyResult<()> {
+        self.check_writable("write_mem16()")?;
+        check_alignment(address, 16)?;
+        if byte_swap {
+            values.iter_mut().for_each(|v| *v = v.swap_bytes());
+        }
+        self.with_core(|core| {
+            retry_transient(self.retry_policy, || core.write_16(address, &values)).map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "Memory write at 0x{:08x} failed: {}",
+                    address, e
+                ))
+            })
+        })
+    }
+
+    /// Read 32-bit words from target memory.
+    ///
+    /// Like the other memory read/write methods, a transiently busy target (WAIT
+    /// responses right after reset or while flash is being programmed) is retried
+    /// a few times before the read fails; bus faults fail immediately.
+    ///
+    /// Args:
+    ///     address: Start address (must be 4-byte aligned)
+    ///     count: Number of words to read (default 1)
+    ///
+    /// Returns:
+    ///     list[int]: Words read, in target byte order
+    ///
+    /// Raises:
+    ///     ValueError: If the address is not 4-byte aligned
+    ///     RuntimeError: If not attached or the memory read fails
+    ///
+    /// Example:
+    ///     >>> cpuid = session.read_mem32(0xE000ED00)[0]
+    #[pyo3(signature = (address, count=1))]
+    fn read_mem32(&self, address: u64, count: usize) -> PyResult<Vec<u32>> {
+        check_alignment(address, 32)?;
+        self.with_core(|core| {
+            let mut words = vec![0u32; count];
+            retry_transient(self.retry_policy, || core.read_32(address, &mut words)).map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "Memory read at 0x{:08x} failed: {}",
+                    address, e
+                ))
+            })?;
+            Ok(words)
+        })
+    }
+
+    /// Read 32-bit words one at a time, tolerating addresses that fault.
+    ///
+    /// `read_mem32()` fails as a whole if any word in the range faults (unmapped
+    /// or unclocked peripheral, reserved hole). This reads word by word and
+    /// returns None for each word whose read bus-faulted, so exploratory register
+    /// scans and memory-map discovery still get partial results. Transient errors
+    /// are retried like any other read, and other failures raise. It is much
+    /// slower than a block read; use it only on ranges that may fault.
+    ///
+    /// Args:
+    ///     address: Start address (must be 4-byte aligned)
+    ///     count: Number of words to read
+    ///
+    /// Returns:
+    ///     list[int | None]: Word values, None where the read faulted
+    ///
+    /// Raises:
+    ///     ValueError: If the address is not 4-byte aligned
+    ///     RuntimeError: If not attached, or a read fails other than by faulting
+    ///     ProbeDisconnectedError: If the probe went away during the scan
+    ///
+    /// Example:
+    ///     >>> words = session.read_mem32_lenient(0x40000000, 256)
+    ///     >>> mapped = [hex(0x40000000 + 4 * i) for i, w in enumerate(words) if w is not None]
+    fn read_mem32_lenient(&self, address: u64, count: usize) -> PyResult<Vec<Option<u32>>> {
+        check_alignment(address, 32)?;
+        self.with_core(|core| {
+            let mut words = Vec::new();
+            for i in 0..count as u64 {
+                let word_address = address + 4 * i;
+                match retry_transient(self.retry_policy, || core.read_word_32(word_address)) {
+                    Ok(word) => words.push(Some(word)),
+                    Err(e) if is_bus_fault(&e) => words.push(None),
+                    // Anything else (a dead probe, a stuck debug port) would fail
+                    // every remaining word too, so report it instead of a list of Nones
+                    Err(e) => {
+                        return Err(pyo3::exceptions::PyRuntimeError::new_err(format!(
+                            "Failed to read memory at 0x{:08x}: {}",
+                            word_address, e
+                        )))
+                    }
+                }
+            }
+            Ok(words)
+        })
+    }
+
+    /// Write 32-bit words to target memory.
+    ///
+    /// Args:
+    ///     address: Start address (must be 4-byte aligned)
+    ///     values: Words to write
+    ///     byte_swap: Reverse the byte order of each word before writing, for
+    ///         data captured on a system of the other endianness (default False)
+    ///
+    /// Raises:
+    ///     ValueError: If the address is not 4-byte aligned
+    ///     RuntimeError: If not attached or the memory write fails
+    ///
+    /// Example:
+    ///     >>> session.write_mem32(0x20000000, [0xDEADBEEF, 0x12345678])
+    ///     >>> session.write_mem32(0x20000000, [0xEFBEADDE], byte_swap=True)  # writes 0xDEADBEEF
+    #[pyo3(signature = (address, values, byte_swap=false))]
+    fn write_mem32(&self, address: u64, mut values: Vec<u32>, byte_swap: bool) -> PyResult<()> {
+        self.check_writable("write_mem32()")?;
+        check_alignment(address, 32)?;
+        if byte_swap {
+            values.iter_mut().for_each(|v| *v = v.swap_bytes());
+        }
+        self.with_core(|core| {
+            retry_transient(self.retry_policy, || core.write_32(address, &values)).map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "Memory write at 0x{:08x} failed: {}",
+                    address, e
+                ))
+            })
+        })
+    }
+
+    /// Read several named globals in one pass.
+    ///
+    /// All symbols are resolved from a single parse of the ELF, then read under a
+    /// single core attach — the operation a variable-watch panel needs on every
+    /// refresh.
+    ///
+    /// Args:
+    ///     elf_path: Path to the ELF file the running firmware was built from
+    ///     specs: List of (symbol_name, type) tuples. Type is one of
+    ///         "u8", "i8", "u16", "i16", "u32", "i32", "u64", "i64", "f32", "f64", "bool"
+    ///
+    /// Returns:
+    ///     dict[str, int | float | bool]: Symbol name → current value
+    ///
+    /// Raises:
+    ///     ValueError: If a type is unknown or any symbol is missing from the ELF
+    ///     RuntimeError: If not attached or a memory read fails
+    ///
+    /// Example:
+    ///     >>> values = session.read_symbols("zephyr.elf", [("uptime_ms", "u32"), ("temp_c", "f32")])
+    ///     >>> print(values["temp_c"])
+    fn read_symbols<'py>(
+        &self,
+        py: Python<'py>,
+        elf_path: &str,
+        specs: Vec<(String, String)>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let types = specs
+            .iter()
+            .map(|(_, ty)| ValueType::parse(ty))
+            .collect::<PyResult<Vec<_>>>()?;
+        let names: Vec<&str> = specs.iter().map(|(name, _)| name.as_str()).collect();
+        let addresses = find_symbols(elf_path, &names)?;
+
+        let raw_values = self.with_core(|core| {
+            let mut values = Vec::with_capacity(specs.len());
+            for (name, ty) in names.iter().zip(&types) {
+                let address = addresses[*name];
+                let mut bytes = vec![0u8; ty.size()];
+                retry_transient(self.retry_policy, || core.read(address, &mut bytes)).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Failed to read '{}' at 0x{:08x}: {}",
+                        name, address, e
+                    ))
+                })?;
+                values.push(bytes);
+            }
+            Ok(values)
+        })?;
+
+        let dict = PyDict::new(py);
+        for ((name, ty), bytes) in names.iter().zip(&types).zip(&raw_values) {
+            dict.set_item(*name, ty.decode(py, bytes)?)?;
+        }
+        Ok(dict)
+    }
+
+    /// Check whether the firmware on the target matches an ELF file.
+    ///
+    /// This is a quick sanity check, not a full verify: for each code/rodata section
+    /// in the ELF it compares a window at the start and at the end of the section
+    /// against target memory. That catches "wrong build flashed" in a few small reads,
+    /// before symbols from one build get used against a device running another.
+    ///
+    /// Args:
+    ///     elf_path: Path to the ELF file expected to be running
+    ///
+    /// Returns:
+    ///     bool: True if every sampled window matches
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached or a memory read fails
+    ///     ValueError: If the ELF has no loadable code sections
+    ///
+    /// Example:
+    ///     >>> if not session.matches_elf("build/zephyr/zephyr.elf"):
+    ///     ...     print("Device is running a different build")
+    fn matches_elf(&self, elf_path: &str) -> PyResult<bool> {
+        // Bytes compared at each end of a section
+        const WINDOW: usize = 256;
+
+        let sections = image_sections(elf_path)?;
+        if sections.is_empty() {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "No loadable code sections found in ELF file '{}'",
+                elf_path
+            )));
+        }
+
+        self.with_core(|core| {
+            for section in &sections {
+                let len = section.data.len();
+                let head = 0..len.min(WINDOW);
+                let tail = len.saturating_sub(WINDOW)..len;
+
+                for window in [head, tail] {
+                    let mut buffer = vec![0u8; window.len()];
+                    let address = section.address + window.start as u64;
+                    retry_transient(self.retry_policy, || core.read(address, &mut buffer)).map_err(|e| {
+                        pyo3::exceptions::PyRuntimeError::new_err(format!(
+                            "Failed to read section '{}' at 0x{:08x}: {}",
+                            section.name, address, e
+                        ))
+                    })?;
+                    if buffer[..] != section.data[window] {
+                        return Ok(false);
+                    }
+                }
+            }
+            Ok(true)
+        })
+    }
+
+    /// Compute a CRC over a region of target memory.
+    ///
+    /// The region is read in chunks and checksummed in Rust, so large regions never
+    /// have to be marshalled into Python just to be hashed. Complements `matches_elf`
+    /// for "does the device match?" checks on arbitrary regions (a config page, a
+    /// flash slot, a calibration table); compare against `zlib.crc32()` of the
+    /// expected bytes on the host.
+    ///
+    /// Args:
+    ///     address: Start address of the region
+    ///     length: Number of bytes to checksum
+    ///     algorithm: "crc32" (zlib-compatible, default) or "crc32c"
+    ///
+    /// Returns:
+    ///     int: The CRC of the region
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached or a memory read fails
+    ///     ValueError: If the algorithm is unknown
+    ///
+    /// Example:
+    ///     >>> import zlib
+    ///     >>> expected = zlib.crc32(open("app.bin", "rb").read())
+    ///     >>> if session.crc_memory(0x08000000, 65536) != expected:
+    ///     ...     print("Flash contents differ")
+    #[pyo3(signature = (address, length, algorithm="crc32"))]
+    fn crc_memory(&self, address: u64, length: usize, algorithm: &str) -> PyResult<u32> {
+        const CHUNK: usize = 4096;
+
+        let algorithm = CrcAlgorithm::parse(algorithm)?;
+
+        self.with_core(|core| {
+            let mut crc = 0xFFFF_FFFF;
+            let mut buffer = vec![0u8; CHUNK.min(length)];
+            let mut offset = 0;
+            while offset < length {
+                let chunk = &mut buffer[..CHUNK.min(length - offset)];
+                let chunk_address = address + offset as u64;
+                retry_transient(self.retry_policy, || core.read(chunk_address, chunk)).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Memory read at 0x{:08x} failed: {}",
+                        chunk_address, e
+                    ))
+                })?;
+                crc = algorithm.update(crc, chunk);
+                offset += chunk.len();
+            }
+            Ok(crc ^ 0xFFFF_FFFF)
+        })
+    }
+
+    /// Read and decode the Cortex-M CPUID register (0xE000ED00).
+    ///
+    /// Useful for logging the exact core and silicon revision when reproducing
+    /// revision-specific bugs, and for confirming the core matches the chip probe-rs
+    /// attached as.
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         raw: int raw CPUID value
+    ///         implementer: int implementer code (0x41 = ARM)
+    ///         architecture: int architecture field (0xC = ARMv6-M, 0xF = ARMv7-M/ARMv8-M)
+    ///         part_number: int primary part number (e.g. 0xC24)
+    ///         core: str | None core name (e.g. "Cortex-M4"), None if unknown
+    ///         variant: int major revision (N in rNpM)
+    ///         revision: int minor revision (M in rNpM)
+    ///         revision_string: str e.g. "r0p1"
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached or the read fails
+    ///
+    /// Example:
+    ///     >>> cpuid = session.read_cpuid()
+    ///     >>> print(cpuid["core"], cpuid["revision_string"])
+    fn read_cpuid<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let raw = self.with_core(|core| read_reg(core, self.retry_policy, cortex_m::CPUID))?;
+        let cpuid = cortex_m::Cpuid::decode(raw);
+
+        let dict = PyDict::new(py);
+        dict.set_item("raw", raw)?;
+        dict.set_item("implementer", cpuid.implementer)?;
+        dict.set_item("architecture", cpuid.architecture)?;
+        dict.set_item("part_number", cpuid.part_number)?;
+        dict.set_item("core", cpuid.core_name())?;
+        dict.set_item("variant", cpuid.variant)?;
+        dict.set_item("revision", cpuid.revision)?;
+        dict.set_item("revision_string", cpuid.revision_string())?;
+        Ok(dict)
+    }
+
+    /// Read and decode the MPU configuration.
+    ///
+    /// Lists every enabled MPU region with its address range and access
+    /// permissions, for diagnosing MemManage faults (which region the faulting
+    /// address fell into, and whether it allowed the access). Handles both the
+    /// ARMv7-M MPU (RBAR/RASR, power-of-two regions with subregions) and the
+    /// ARMv8-M MPU (RBAR/RLAR base/limit regions), chosen from CPUID.
+    ///
+    /// Regions are selected through MPU_RNR, which is restored afterwards. Halt the
+    /// core first if the firmware may be reprogramming the MPU concurrently. On
+    /// read-only sessions RNR is never written, so only the region it currently
+    /// selects is decoded (all_regions is False).
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         architecture: str "armv7m" or "armv8m"
+    ///         num_regions: int regions implemented (0 = no MPU)
+    ///         enabled: bool MPU_CTRL.ENABLE
+    ///         hfnmi_enabled: bool MPU_CTRL.HFNMIENA (MPU active in HardFault/NMI)
+    ///         default_map_enabled: bool MPU_CTRL.PRIVDEFENA (background region for
+    ///             privileged code)
+    ///         all_regions: bool False if only the region selected by MPU_RNR was
+    ///             read (read-only sessions)
+    ///         regions: list[dict], one per enabled region, with keys:
+    ///             region: int region number
+    ///             base: int first address
+    ///             limit: int last address (inclusive)
+    ///             privileged: str "rw", "ro" or "none"
+    ///             unprivileged: str "rw", "ro" or "none"
+    ///             executable: bool False if XN is set
+    ///             subregion_disable: int | None SRD bits (ARMv7-M only)
+    ///             attributes: str memory attributes, e.g. "TEX=0 C=1 B=0 S=0"
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached or a register access fails
+    ///
+    /// Example:
+    ///     >>> for r in session.read_mpu_config()["regions"]:
+    ///     ...     print(f"{r['region']}: {r['base']:#010x}-{r['limit']:#010x} {r['privileged']}/{r['unprivileged']}")
+    fn read_mpu_config<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let (armv8m, type_reg, ctrl, regions) = self.with_core(|core| {
+            let armv8m = cortex_m::Cpuid::decode(read_reg(core, self.retry_policy, cortex_m::CPUID)?).is_armv8m();
+            let type_reg = read_reg(core, self.retry_policy, cortex_m::MPU_TYPE)?;
+            let num_regions = (type_reg >> 8) & 0xFF;
+            // CTRL, RNR, RBAR, RASR/RLAR
+            let regs = read_regs(core, self.retry_policy, cortex_m::MPU_CTRL, 4)?;
+            let saved_rnr = regs[1];
+
+            let write_rnr = |core: &mut Core, region: u32| {
+                core.write_word_32(cortex_m::MPU_RNR, region).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to select MPU region {}: {}", region, e))
+                })
+            };
+
+            let decode = |rbar: u32, attrs: u32| {
+                if armv8m {
+                    cortex_m::decode_mpu_region_v8(rbar, attrs)
+                } else {
+                    cortex_m::decode_mpu_region_v7(rbar, attrs)
+                }
+            };
+
+            let mut regions = Vec::new();
+            if self.read_only {
+                if saved_rnr < num_regions {
+                    if let Some(decoded) = decode(regs[2], regs[3]) {
+                        regions.push((saved_rnr, decoded));
+                    }
+                }
+            } else {
+                for region in 0..num_regions {
+                    write_rnr(core, region)?;
+                    let words = read_regs(core, self.retry_policy, cortex_m::MPU_RNR + 4, 2)?;
+                    if let Some(decoded) = decode(words[0], words[1]) {
+                        regions.push((region, decoded));
+                    }
+                }
+                if num_regions > 0 {
+                    write_rnr(core, saved_rnr)?;
+                }
+            }
+
+            Ok((armv8m, type_reg, regs[0], regions))
+        })?;
+
+        let list = PyList::empty(py);
+        for (region, decoded) in regions {
+            let entry = PyDict::new(py);
+            entry.set_item("region", region)?;
+            entry.set_item("base", decoded.base)?;
+            entry.set_item("limit", decoded.limit)?;
+            entry.set_item("privileged", decoded.privileged)?;
+            entry.set_item("unprivileged", decoded.unprivileged)?;
+            entry.set_item("executable", decoded.executable)?;
+            entry.set_item("subregion_disable", decoded.subregion_disable)?;
+            entry.set_item("attributes", decoded.attributes)?;
+            list.append(entry)?;
+        }
+
+        let dict = PyDict::new(py);
+        dict.set_item("architecture", if armv8m { "armv8m" } else { "armv7m" })?;
+        dict.set_item("num_regions", (type_reg >> 8) & 0xFF)?;
+        dict.set_item("enabled", ctrl & 1 != 0)?;
+        dict.set_item("hfnmi_enabled", ctrl & (1 << 1) != 0)?;
+        dict.set_item("default_map_enabled", ctrl & (1 << 2) != 0)?;
+        dict.set_item("all_regions", !self.read_only)?;
+        dict.set_item("regions", list)?;
+        Ok(dict)
+    }
+
+    /// Read the number of interrupt lines the NVIC implements.
+    ///
+    /// Decodes ICTR.INTLINESNUM. The count comes in steps of 32, so it is an upper
+    /// bound: a part with 68 IRQs reports 96. ARMv6-M cores have no ICTR and
+    /// report 32, their architectural maximum.
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         raw: int raw ICTR value
+    ///         irq_count: int number of implemented interrupt lines
+    ///         register_words: int number of 32-bit words in each NVIC bitmap
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached or the read fails
+    ///
+    /// Example:
+    ///     >>> print(session.read_irq_count()["irq_count"])
+    fn read_irq_count<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let raw = self.with_core(|core| read_reg(core, self.retry_policy, cortex_m::ICTR))?;
+        let irq_count = cortex_m::ictr_irq_lines(raw);
+
+        let dict = PyDict::new(py);
+        dict.set_item("raw", raw)?;
+        dict.set_item("irq_count", irq_count)?;
+        dict.set_item("register_words", irq_count / 32)?;
+        Ok(dict)
+    }
+
+    /// Read and decode the NVIC interrupt state.
+    ///
+    /// Reads the NVIC set-enable, set-pending and active-bit registers plus
+    /// ICSR.VECTACTIVE (which mirrors IPSR, so the core does not need to be halted).
+    ///
+    /// Args:
+    ///     num_irqs: Number of external interrupt lines to decode. Rounded up to a
+    ///         multiple of 32. Defaults to the count the NVIC reports in ICTR (see
+    ///         read_irq_count()); larger values are clamped to it, since reading
+    ///         past the implemented NVIC registers can fault on some parts.
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         enabled: list[int] of enabled IRQ numbers
+    ///         pending: list[int] of pending IRQ numbers
+    ///         active: list[int] of active (executing or preempted) IRQ numbers
+    ///         current_exception: int exception number currently executing (0 = thread mode)
+    ///         current_exception_name: str e.g. "Thread", "HardFault", "IRQ5"
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached or a register read fails
+    ///
+    /// Example:
+    ///     >>> state = session.read_interrupt_state()
+    ///     >>> print(state["pending"], state["current_exception_name"])
+    #[pyo3(signature = (num_irqs=None))]
+    fn read_interrupt_state<'py>(&self, py: Python<'py>, num_irqs: Option<usize>) -> PyResult<Bound<'py, PyDict>> {
+        let (enabled, pending, active, icsr) = self.with_core(|core| {
+            let implemented = cortex_m::ictr_irq_lines(read_reg(core, self.retry_policy, cortex_m::ICTR)?);
+            let words = num_irqs.unwrap_or(implemented).min(implemented).div_ceil(32);
+            Ok((
+                read_regs(core, self.retry_policy, cortex_m::NVIC_ISER, words)?,
+                read_regs(core, self.retry_policy, cortex_m::NVIC_ISPR, words)?,
+                read_regs(core, self.retry_policy, cortex_m::NVIC_IABR, words)?,
+                read_reg(core, self.retry_policy, cortex_m::ICSR)?,
+            ))
+        })?;
+
+        let current = icsr & 0x1FF;
+        let dict = PyDict::new(py);
+        dict.set_item("enabled", cortex_m::irq_bits(&enabled))?;
+        dict.set_item("pending", cortex_m::irq_bits(&pending))?;
+        dict.set_item("active", cortex_m::irq_bits(&active))?;
+        dict.set_item("current_exception", current)?;
+        dict.set_item("current_exception_name", cortex_m::exception_name(current))?;
+        Ok(dict)
+    }
+
+    /// Read the flash readout-protection state from the option bytes.
+    ///
+    /// Family-aware: the register and encoding depend on the chip series
+    /// (STM32 FLASH_OPTR/OPTCR/OBR RDP level, nRF UICR.APPROTECT). Use this before
+    /// reading flash on an unknown device — on a protected part those reads fail
+    /// with unhelpful probe errors.
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         family: str chip series (e.g. "STM32L4")
+    ///         register: str register that was read (e.g. "FLASH_OPTR")
+    ///         address: int register address
+    ///         raw: int raw register value
+    ///         level: int STM32 RDP level (0, 1, 2), or None on nRF
+    ///         readout_protected: bool True if flash readout is blocked
+    ///
+    /// Raises:
+    ///     NotImplementedError: If the chip's family has no known register map
+    ///     RuntimeError: If not attached or the read fails
+    ///
+    /// Example:
+    ///     >>> prot = session.read_flash_protection()
+    ///     >>> if prot["readout_protected"]:
+    ///     ...     print(f"RDP level {prot['level']}")
+    fn read_flash_protection<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let family = self.family()?;
+        let register = family::protection_register(family).ok_or_else(|| {
+            self.unsupported_family(family, "Reading flash protection")
+        })?;
+
+        let raw = self.with_core(|core| read_reg(core, self.retry_policy, register.address))?;
+        let protection = family::decode_protection(family, raw);
+
+        let dict = PyDict::new(py);
+        dict.set_item("family", family.name())?;
+        dict.set_item("register", register.name)?;
+        dict.set_item("address", register.address)?;
+        dict.set_item("raw", raw)?;
+        dict.set_item("level", protection.level)?;
+        dict.set_item("readout_protected", protection.readout_protected)?;
+        Ok(dict)
+    }
+
+    /// Read the flash write protection and report which sectors/pages it covers.
+    ///
+    /// Family-aware: decodes the STM32 nWRP option bits (F0/F1/F3 FLASH_WRPR,
+    /// F2/F4/F7 FLASH_OPTCR, H7 FLASH_WPSN_CUR1R) or WRP area registers
+    /// (G0/G4/L4/WB/WL FLASH_WRP1AR/BR). Programming a protected sector fails with a
+    /// generic flash error, so check this first when a flash write is refused. Only
+    /// bank 1 is reported on dual-bank parts.
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         family: str chip series (e.g. "STM32F4")
+    ///         unit: str what the numbers count: "sector", "page" or "page_group"
+    ///             (F0/F1/F3: each bit covers a density-dependent group of pages)
+    ///         registers: list[dict] per register, with keys name, address, raw
+    ///             and protected (list[int] of protected units)
+    ///         protected: list[int] all protected units, sorted
+    ///         write_protected: bool True if any unit is protected
+    ///
+    /// Raises:
+    ///     NotImplementedError: If the chip's family has no known register map
+    ///     RuntimeError: If not attached or the read fails
+    ///
+    /// Example:
+    ///     >>> wrp = session.read_write_protection()
+    ///     >>> print(wrp["unit"], wrp["protected"])
+    fn read_write_protection<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let family = self.family()?;
+        let protection = family::write_protection(family).ok_or_else(|| {
+            self.unsupported_family(family, "Reading flash write protection")
+        })?;
+
+        let raws = self.with_core(|core| {
+            protection
+                .registers
+                .iter()
+                .map(|&(_, address)| read_reg(core, self.retry_policy, address))
+                .collect::<PyResult<Vec<u32>>>()
+        })?;
+
+        let registers = PyList::empty(py);
+        let mut all_protected = Vec::new();
+        for (&(name, address), raw) in protection.registers.iter().zip(raws) {
+            let protected = family::decode_write_protection(family, &protection.encoding, raw);
+            let entry = PyDict::new(py);
+            entry.set_item("name", name)?;
+            entry.set_item("address", address)?;
+            entry.set_item("raw", raw)?;
+            entry.set_item("protected", protected.clone())?;
+            registers.append(entry)?;
+            all_protected.extend(protected);
+        }
+        all_protected.sort_unstable();
+        all_protected.dedup();
+
+        let dict = PyDict::new(py);
+        dict.set_item("family", family.name())?;
+        dict.set_item("unit", protection.unit)?;
+        dict.set_item("registers", registers)?;
+        dict.set_item("write_protected", !all_protected.is_empty())?;
+        dict.set_item("protected", all_protected)?;
+        Ok(dict)
+    }
+
+    /// Read why the target last reset (watchdog, brownout, software, pin, ...).
+    ///
+    /// Family-aware: reads STM32 RCC_CSR/RCC_RSR, nRF RESETREAS, or the ESP32
+    /// RTC_CNTL reset state, and decodes it into reason names. STM32 and nRF flags
+    /// are sticky — they accumulate across resets until firmware clears them — so
+    /// more than one reason may be reported.
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         family: str chip series (e.g. "nRF52")
+    ///         register: str register that was read
+    ///         raw: int raw register value
+    ///         reasons: list[str] e.g. ["independent_watchdog", "pin"]
+    ///
+    /// Raises:
+    ///     NotImplementedError: If the chip's family has no known register map
+    ///     RuntimeError: If not attached or the read fails
+    ///
+    /// Example:
+    ///     >>> cause = session.read_reset_cause()
+    ///     >>> if "brownout" in cause["reasons"]:
+    ///     ...     print("Check the supply")
+    fn read_reset_cause<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let family = self.family()?;
+        let register = family::reset_cause_register(family)
+            .ok_or_else(|| self.unsupported_family(family, "Reading the reset cause"))?;
+
+        let raw = self.with_core(|core| read_reg(core, self.retry_policy, register.address))?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("family", family.name())?;
+        dict.set_item("register", register.name)?;
+        dict.set_item("raw", raw)?;
+        dict.set_item("reasons", family::decode_reset_cause(&register.encoding, raw))?;
+        Ok(dict)
+    }
+
+    /// Read the Zephyr version and build id of the firmware running on the target.
+    ///
+    /// Zephyr has no data symbol holding its version, but the boot banner literal
+    /// ("*** Booting Zephyr OS build v3.6.0 ***") embeds it. Its address is found in
+    /// the ELF and the version is read back from the device, so a mismatching build
+    /// shows up as a different (or garbled) version. If the firmware was linked with
+    /// a GNU build-id, the id is compared against device memory too (only possible
+    /// when the note section is allocated into the image).
+    ///
+    /// Args:
+    ///     elf_path: Path to the Zephyr ELF (e.g. "build/zephyr/zephyr.elf")
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         version: str | None version string read from the device
+    ///         elf_version: str | None version string from the ELF
+    ///         build_id: str | None hex GNU build-id from the ELF
+    ///         build_id_matches: bool | None True/False if the build-id is in target
+    ///             memory and was compared, None otherwise
+    ///
+    /// Raises:
+    ///     ValueError: If the ELF has neither a Zephyr banner nor a build-id
+    ///     RuntimeError: If not attached or a memory read fails
+    ///
+    /// Example:
+    ///     >>> info = session.read_zephyr_version("build/zephyr/zephyr.elf")
+    ///     >>> assert info["version"] == info["elf_version"]
+    fn read_zephyr_version<'py>(&self, py: Python<'py>, elf_path: &str) -> PyResult<Bound<'py, PyDict>> {
+        // Locate the version inside the banner literal: (address, length, text)
+        let banner = image_sections(elf_path)?.into_iter().find_map(|section| {
+            let offset = section
+                .data
+                .windows(ZEPHYR_BANNER.len())
+                .position(|w| w == ZEPHYR_BANNER)?
+                + ZEPHYR_BANNER.len();
+            let len = section.data[offset..]
+                .iter()
+                .position(|&b| b == b' ' || b == 0)?;
+            let text = String::from_utf8_lossy(&section.data[offset..offset + len]).to_string();
+            Some((section.address + offset as u64, len, text))
+        });
+        let build_id = gnu_build_id(elf_path)?;
+
+        if banner.is_none() && build_id.is_none() {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "No Zephyr boot banner or GNU build-id found in ELF file '{}'",
+                elf_path
+            )));
+        }
+
+        let (version, build_id_matches) = self.with_core(|core| {
+            let version = match banner {
+                Some((address, len, _)) => {
+                    let mut bytes = vec![0u8; len];
+                    retry_transient(self.retry_policy, || core.read(address, &mut bytes)).map_err(|e| {
+                        pyo3::exceptions::PyRuntimeError::new_err(format!(
+                            "Failed to read version at 0x{:08x}: {}",
+                            address, e
+                        ))
+                    })?;
+                    Some(String::from_utf8_lossy(&bytes).to_string())
+                }
+                None => None,
+            };
+
+            let build_id_matches = match build_id {
+                Some((address, ref id)) if address != 0 => {
+                    let mut bytes = vec![0u8; id.len()];
+                    retry_transient(self.retry_policy, || core.read(address, &mut bytes)).map_err(|e| {
+                        pyo3::exceptions::PyRuntimeError::new_err(format!(
+                            "Failed to read build-id at 0x{:08x}: {}",
+                            address, e
+                        ))
+                    })?;
+                    Some(bytes == *id)
+                }
+                _ => None,
+            };
+
+            Ok((version, build_id_matches))
+        })?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("version", version)?;
+        dict.set_item("elf_version", banner.map(|(_, _, text)| text))?;
+        dict.set_item("build_id", build_id.map(|(_, id)| to_hex(&id)))?;
+        dict.set_item("build_id_matches", build_id_matches)?;
+        Ok(dict)
+    }
+
+    /// Keep the debug unit clocked while the firmware sleeps.
+    ///
+    /// Many parts gate the debug clock in Stop/Standby, so RTT and memory access
+    /// stop working as soon as the firmware enters a low-power mode. This sets the
+    /// family's DBGMCU_CR debug-in-low-power bits (STM32 DBG_SLEEP/DBG_STOP/
+    /// DBG_STANDBY) with a read-modify-write. The setting survives until the next
+    /// power-on reset, and increases power consumption in low-power modes.
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         family: str chip series
+    ///         register: str register that was written (e.g. "DBGMCU_CR")
+    ///         address: int register address
+    ///         value: int register value after the update
+    ///
+    /// Raises:
+    ///     NotImplementedError: If the family has no such control (e.g. nRF keeps
+    ///         debug powered on its own while a debugger is connected)
+    ///     RuntimeError: If not attached or the access fails
+    ///
+    /// Example:
+    ///     >>> session.enable_debug_in_low_power()
+    ///     >>> session.start_rtt(elf_path="build/zephyr/zephyr.elf")
+    fn enable_debug_in_low_power<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        self.check_writable("enable_debug_in_low_power()")?;
+        let family = self.family()?;
+        let register = family::low_power_debug_register(family)
+            .ok_or_else(|| self.unsupported_family(family, "Enabling debug in low-power modes"))?;
+
+        let value = self.with_core(|core| {
+            let value = read_reg(core, self.retry_policy, register.address)? | register.bits;
+            core.write_word_32(register.address, value).map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "Failed to write {} at 0x{:08x}: {}",
+                    register.name, register.address, e
+                ))
+            })?;
+            Ok(value)
+        })?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("family", family.name())?;
+        dict.set_item("register", register.name)?;
+        dict.set_item("address", register.address)?;
+        dict.set_item("value", value)?;
+        Ok(dict)
+    }
+
+    /// Read the target's real-time clock as a Python datetime.
+    ///
+    /// Decodes the STM32 calendar RTC (RTC_TR/RTC_DR, BCD). The RTC must be in
+    /// 24-hour format, the HAL default. The result is naive: the RTC has no notion
+    /// of time zone, so it means whatever the firmware treats it as.
+    ///
+    /// Returns:
+    ///     datetime.datetime: Current RTC date and time (second resolution)
+    ///
+    /// Raises:
+    ///     NotImplementedError: If the family has no calendar RTC (STM32F1 and nRF
+    ///         only have tick counters)
+    ///     RuntimeError: If not attached, the read fails or the RTC holds an
+    ///         invalid date (e.g. never initialized)
+    ///
+    /// Example:
+    ///     >>> print(session.read_rtc())
+    ///     2026-03-14 15:09:26
+    fn read_rtc<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDateTime>> {
+        let family = self.family()?;
+        let base = family::rtc_base(family).ok_or_else(|| self.unsupported_family(family, "Reading the RTC"))?;
+
+        // Reading TR locks the DR shadow register until DR is read, so the pair
+        // is consistent as long as TR comes first
+        let regs = self.with_core(|core| read_regs(core, self.retry_policy, base + family::RTC_TR, 2))?;
+        let rtc = family::RtcDateTime::decode(regs[0], regs[1]);
+
+        PyDateTime::new(
+            py,
+            i32::from(rtc.year),
+            rtc.month,
+            rtc.day,
+            rtc.hour,
+            rtc.minute,
+            rtc.second,
+            0,
+            None,
+        )
+        .map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!(
+                "RTC holds an invalid date (TR=0x{:08x}, DR=0x{:08x}): {}",
+                regs[0], regs[1], e
+            ))
+        })
+    }
+
+    /// Set the target's real-time clock from a Python datetime.
+    ///
+    /// Unlocks the RTC write protection, enters initialization mode, writes
+    /// RTC_TR/RTC_DR and exits again, the same sequence the STM32 HAL uses.
+    /// Microseconds and tzinfo are ignored. The RTC lives in the backup domain, so
+    /// the firmware must already have enabled backup-domain writes (PWR DBP) and
+    /// the RTC clock, which any firmware using the RTC does at startup.
+    ///
+    /// Args:
+    ///     datetime: Date and time to set (year 2000-2099)
+    ///
+    /// Raises:
+    ///     NotImplementedError: If the family has no calendar RTC
+    ///     PermissionError: On a read-only session
+    ///     ValueError: If the year is outside 2000-2099
+    ///     RuntimeError: If not attached, an access fails, or the RTC doesn't
+    ///         enter initialization mode (backup domain still write-protected or
+    ///         RTC not clocked)
+    ///
+    /// Example:
+    ///     >>> from datetime import datetime
+    ///     >>> session.write_rtc(datetime(2026, 12, 31, 23, 59, 50))  # test the rollover
+    fn write_rtc(&self, datetime: &Bound<'_, PyDateTime>) -> PyResult<()> {
+        const INIT_TIMEOUT: Duration = Duration::from_millis(100);
+
+        self.check_writable("write_rtc()")?;
+        let family = self.family()?;
+        let base = family::rtc_base(family).ok_or_else(|| self.unsupported_family(family, "Writing the RTC"))?;
+
+        let year = datetime.get_year();
+        if !(2000..=2099).contains(&year) {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "RTC year must be 2000-2099, got {}",
+                year
+            )));
+        }
+        let rtc = family::RtcDateTime {
+            year: year as u16,
+            month: datetime.get_month(),
+            day: datetime.get_day(),
+            weekday: datetime.call_method0("isoweekday")?.extract()?,
+            hour: datetime.get_hour(),
+            minute: datetime.get_minute(),
+            second: datetime.get_second(),
+        };
+        let (tr, dr) = rtc.encode();
+
+        self.with_core(|core| {
+            let write = |core: &mut Core, offset: u64, value: u32| {
+                core.write_word_32(base + offset, value).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Failed to write RTC register at 0x{:08x}: {}",
+                        base + offset,
+                        e
+                    ))
+                })
+            };
+
+            for key in family::RTC_WPR_UNLOCK {
+                write(core, family::RTC_WPR, key)?;
+            }
+
+            let isr = read_reg(core, self.retry_policy, base + family::RTC_ISR)?;
+            write(core, family::RTC_ISR, isr | family::RTC_ISR_INIT)?;
+            let deadline = Instant::now() + INIT_TIMEOUT;
+            while read_reg(core, self.retry_policy, base + family::RTC_ISR)? & family::RTC_ISR_INITF == 0 {
+                if Instant::now() >= deadline {
+                    write(core, family::RTC_ISR, isr & !family::RTC_ISR_INIT)?;
+                    write(core, family::RTC_WPR, family::RTC_WPR_LOCK)?;
+                    return Err(pyo3::exceptions::PyRuntimeError::new_err(
+                        "RTC did not enter initialization mode. The backup domain may be \
+                         write-protected (PWR DBP not set) or the RTC clock not running.",
+                    ));
+                }
+            }
+
+            write(core, family::RTC_TR, tr)?;
+            write(core, family::RTC_DR, dr)?;
+
+            let isr = read_reg(core, self.retry_policy, base + family::RTC_ISR)?;
+            write(core, family::RTC_ISR, isr & !family::RTC_ISR_INIT)?;
+            write(core, family::RTC_WPR, family::RTC_WPR_LOCK)
+        })
+    }
+
+    /// Decode the hardware-stacked Cortex-M exception frame at a stack pointer.
+    ///
+    /// The first step of a fault backtrace: on exception entry the core pushes
+    /// R0-R3, R12, LR, PC and xPSR (plus S0-S15 and FPSCR if the interrupted code
+    /// had active FPU state). Which layout was used is encoded in the EXC_RETURN
+    /// value found in LR inside the handler — pass it as `exc_return` so the
+    /// extended frame is detected. Without it, a basic frame is assumed.
+    ///
+    /// Args:
+    ///     sp: Stack pointer at exception entry (MSP or PSP, per EXC_RETURN bit 2)
+    ///     exc_return: Optional EXC_RETURN value (LR in the handler, e.g. 0xFFFFFFED)
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         r0, r1, r2, r3, r12, lr, pc, xpsr: int stacked register values
+    ///         extended: bool True if the FPU extended frame was decoded
+    ///         s_registers: list[int] raw S0-S15 (extended frame only)
+    ///         s_values: list[float] S0-S15 as floats (extended frame only)
+    ///         fpscr: int stacked FPSCR (extended frame only)
+    ///         fpu_lazy_pending: bool True if lazy stacking is still pending for this
+    ///             frame (FPCCR.LSPACT with FPCAR pointing into it): the stacked
+    ///             S0-S15/FPSCR slots were never written, and the real values are
+    ///             still in the FPU — use read_fpu_registers() (extended frame only)
+    ///         frame_size: int bytes occupied by the frame, including alignment padding
+    ///         original_sp: int stack pointer value before the exception
+    ///
+    /// Raises:
+    ///     ValueError: If sp is not 4-byte aligned
+    ///     RuntimeError: If not attached or the memory read fails
+    ///
+    /// Example:
+    ///     >>> frame = session.read_exception_frame(sp=0x20007FC0, exc_return=0xFFFFFFFD)
+    ///     >>> print(f"Faulting PC: 0x{frame['pc']:08x}")
+    #[pyo3(signature = (sp, exc_return=None))]
+    fn read_exception_frame<'py>(
+        &self,
+        py: Python<'py>,
+        sp: u64,
+        exc_return: Option<u32>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        check_alignment(sp, 32)?;
+        let extended = exc_return.is_some_and(cortex_m::exc_return_has_fpu_frame);
+        let words = if extended {
+            cortex_m::EXTENDED_FRAME_WORDS
+        } else {
+            cortex_m::BASIC_FRAME.len()
+        };
+
+        let (frame, fpu_lazy_pending) = self.with_core(|core| {
+            let frame = read_regs(core, self.retry_policy, sp, words)?;
+            let lazy_pending = if extended {
+                let fpccr = read_reg(core, self.retry_policy, cortex_m::FPCCR)?;
+                let fpcar = read_reg(core, self.retry_policy, cortex_m::FPCAR)?;
+                // S0 follows the 8 basic-frame words
+                fpccr & 1 != 0 && u64::from(fpcar & !0x7) == sp + 32
+            } else {
+                false
+            };
+            Ok((frame, lazy_pending))
+        })?;
+
+        let dict = PyDict::new(py);
+        for (name, value) in cortex_m::BASIC_FRAME.iter().zip(&frame) {
+            dict.set_item(*name, value)?;
+        }
+        dict.set_item("extended", extended)?;
+        if extended {
+            let s_registers = &frame[8..24];
+            let s_values: Vec<f32> = s_registers.iter().map(|&bits| f32::from_bits(bits)).collect();
+            dict.set_item("s_registers", s_registers)?;
+            dict.set_item("s_values", s_values)?;
+            dict.set_item("fpscr", frame[24])?;
+            dict.set_item("fpu_lazy_pending", fpu_lazy_pending)?;
+        }
+
+        let padding = if cortex_m::xpsr_stack_aligned(frame[7]) { 4 } else { 0 };
+        let frame_size = words as u64 * 4 + padding;
+        dict.set_item("frame_size", frame_size)?;
+        dict.set_item("original_sp", sp + frame_size)?;
+        Ok(dict)
+    }
+
+    /// Read the live FPU registers (S0-S31 and FPSCR).
+    ///
+    /// For floating-point fault analysis together with `read_exception_frame()`:
+    /// the stacked frame only holds S0-S15, and with lazy stacking pending even
+    /// those are still live in the FPU. Core registers can only be read while the
+    /// core is halted, so a running core is halted briefly and resumed afterwards;
+    /// a halted core is left as it is. Read-only sessions never halt the core, so
+    /// there the core must already be halted.
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         s_registers: list[int] raw S0-S31 (S0-S15 on single-precision
+    ///             FPUs with 16 registers)
+    ///         s_values: list[float] the same registers as floats
+    ///         fpscr: int FPSCR
+    ///         was_running: bool True if the core was halted for the read
+    ///
+    /// Raises:
+    ///     NotImplementedError: If the core has no FPU
+    ///     RuntimeError: If not attached, or halting or a register read fails
+    ///     PermissionError: If the core is running on a read-only session
+    ///
+    /// Example:
+    ///     >>> fpu = session.read_fpu_registers()
+    ///     >>> print(fpu["s_values"][:4], hex(fpu["fpscr"]))
+    fn read_fpu_registers<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let (s_registers, fpscr, was_running) = self.with_core(|core| {
+            let has_fpu = core.fpu_support().map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to detect FPU: {}", e))
+            })?;
+            if !has_fpu {
+                return Err(pyo3::exceptions::PyNotImplementedError::new_err("Core has no FPU"));
+            }
+
+            // Sn registers in numeric order, then FPSCR
+            let mut s_regs: Vec<(u32, _)> = core
+                .registers()
+                .all_registers()
+                .filter_map(|reg| {
+                    let name = reg.name().to_ascii_lowercase();
+                    name.strip_prefix('s')?.parse::<u32>().ok().map(|n| (n, reg))
+                })
+                .collect();
+            s_regs.sort_by_key(|(n, _)| *n);
+            let fpscr_reg = core
+                .registers()
+                .all_registers()
+                .find(|reg| reg.name().eq_ignore_ascii_case("fpscr"))
+                .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("FPSCR not in core register set"))?;
+
+            let was_running = !core.core_halted().map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to read core status: {}", e))
+            })?;
+            if was_running {
+                self.check_writable("read_fpu_registers() on a running core")?;
+                core.halt(Duration::from_millis(100)).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!("Halt failed: {}", e))
+                })?;
+            }
+
+            let read = |core: &mut Core| -> PyResult<(Vec<u32>, u32)> {
+                let mut values = Vec::with_capacity(s_regs.len());
+                for (n, reg) in &s_regs {
+                    values.push(core.read_core_reg::<u32>(*reg).map_err(|e| {
+                        pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to read S{}: {}", n, e))
+                    })?);
+                }
+                let fpscr = core.read_core_reg::<u32>(fpscr_reg).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to read FPSCR: {}", e))
+                })?;
+                Ok((values, fpscr))
+            };
+            let result = read(core);
+
+            // Resume even if a read failed, so a failed query doesn't leave the
+            // target stopped
+            if was_running {
+                core.run().map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to resume core: {}", e))
+                })?;
+            }
+            let (values, fpscr) = result?;
+            Ok((values, fpscr, was_running))
+        })?;
+
+        let s_values: Vec<f32> = s_registers.iter().map(|&bits| f32::from_bits(bits)).collect();
+        let dict = PyDict::new(py);
+        dict.set_item("s_registers", s_registers)?;
+        dict.set_item("s_values", s_values)?;
+        dict.set_item("fpscr", fpscr)?;
+        dict.set_item("was_running", was_running)?;
+        Ok(dict)
+    }
+
+    /// Read and decode the SCB system control registers.
+    ///
+    /// Reads AIRCR, SCR, CCR and SHPR1-3 (0xE000ED0C-0xE000ED20) in one block and
+    /// decodes the priority grouping, sleep configuration, configuration/trap flags
+    /// and system handler priorities. Useful for priority-inversion and "why does
+    /// this fault / never sleep" questions. On ARMv6-M (Cortex-M0/M0+/M1), AIRCR has
+    /// no PRIGROUP, most CCR bits are fixed and SHPR1 doesn't exist, so those read
+    /// as zero.
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         aircr, scr, ccr: int raw register values
+    ///         big_endian: bool AIRCR.ENDIANNESS
+    ///         priority_group: int AIRCR.PRIGROUP (0-7)
+    ///         preempt_bits: int bits of the 8-bit priority field that select the
+    ///             preemption level (7 - PRIGROUP); the rest are subpriority. Parts
+    ///             implement only the top few bits of the field
+    ///         subpriority_bits: int 8-bit field bits used as subpriority
+    ///         sleep_on_exit: bool SCR.SLEEPONEXIT, sleep on return to thread mode
+    ///         sleep_deep: bool SCR.SLEEPDEEP, WFI/WFE enter deep sleep
+    ///         sev_on_pend: bool SCR.SEVONPEND, pending interrupts wake WFE
+    ///         unaligned_trap: bool CCR.UNALIGN_TRP
+    ///         div_by_zero_trap: bool CCR.DIV_0_TRP
+    ///         stack_align_8: bool CCR.STKALIGN, exception frames 8-byte aligned
+    ///         nonbase_thread_enable: bool CCR.NONBASETHRDENA
+    ///         user_set_pend: bool CCR.USERSETMPEND, unprivileged STIR access
+    ///         bus_fault_ignore: bool CCR.BFHFNMIGN, precise data faults ignored at
+    ///             priority -1 and -2
+    ///         dcache_enabled, icache_enabled, branch_prediction_enabled: bool
+    ///             CCR.DC/IC/BP (Cortex-M7 and ARMv8.1-M; False elsewhere)
+    ///         handler_priorities: dict[str, int] priority byte per system
+    ///             exception, e.g. {"SVCall": 0, "PendSV": 224, "SysTick": 224}
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached or the read fails
+    ///
+    /// Example:
+    ///     >>> sc = session.read_system_control()
+    ///     >>> print(sc["preempt_bits"], sc["handler_priorities"]["PendSV"])
+    fn read_system_control<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let regs = self.with_core(|core| read_regs(core, self.retry_policy, cortex_m::AIRCR, 6))?;
+        let (aircr, scr, ccr, shpr) = (regs[0], regs[1], regs[2], &regs[3..6]);
+        let priority_group = (aircr >> 8) & 0b111;
+
+        let priorities = PyDict::new(py);
+        for number in cortex_m::SHPR_EXCEPTIONS {
+            priorities.set_item(
+                cortex_m::exception_name(number),
+                cortex_m::system_handler_priority(shpr, number),
+            )?;
+        }
+
+        let dict = PyDict::new(py);
+        dict.set_item("aircr", aircr)?;
+        dict.set_item("scr", scr)?;
+        dict.set_item("ccr", ccr)?;
+        dict.set_item("big_endian", aircr & (1 << 15) != 0)?;
+        dict.set_item("priority_group", priority_group)?;
+        dict.set_item("preempt_bits", 7 - priority_group)?;
+        dict.set_item("subpriority_bits", priority_group + 1)?;
+        dict.set_item("sleep_on_exit", scr & (1 << 1) != 0)?;
+        dict.set_item("sleep_deep", scr & (1 << 2) != 0)?;
+        dict.set_item("sev_on_pend", scr & (1 << 4) != 0)?;
+        dict.set_item("nonbase_thread_enable", ccr & 1 != 0)?;
+        dict.set_item("user_set_pend", ccr & (1 << 1) != 0)?;
+        dict.set_item("unaligned_trap", ccr & (1 << 3) != 0)?;
+        dict.set_item("div_by_zero_trap", ccr & (1 << 4) != 0)?;
+        dict.set_item("bus_fault_ignore", ccr & (1 << 8) != 0)?;
+        dict.set_item("stack_align_8", ccr & (1 << 9) != 0)?;
+        dict.set_item("dcache_enabled", ccr & (1 << 16) != 0)?;
+        dict.set_item("icache_enabled", ccr & (1 << 17) != 0)?;
+        dict.set_item("branch_prediction_enabled", ccr & (1 << 18) != 0)?;
+        dict.set_item("handler_priorities", priorities)?;
+        Ok(dict)
+    }
+
+    /// Read and decode the SysTick timer configuration.
+    ///
+    /// Reads SYST_CSR, SYST_RVR, SYST_CVR and SYST_CALIB (0xE000E010-0xE000E01C).
+    /// Helpful when an RTOS tick runs at the wrong rate or not at all.
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         enabled: bool counter enabled (CSR.ENABLE)
+    ///         interrupt_enabled: bool SysTick exception on wrap (CSR.TICKINT)
+    ///         clock_source: str "processor" or "external" (CSR.CLKSOURCE)
+    ///         count_flag: bool counter wrapped since last CSR read (CSR.COUNTFLAG;
+    ///             reading CSR clears it on the target)
+    ///         reload: int reload value (RVR, 24-bit)
+    ///         current: int current counter value (CVR, 24-bit)
+    ///         calibration_tenms: int CALIB.TENMS reload for 10 ms (0 if unknown)
+    ///         calibration_skew: bool CALIB.SKEW, TENMS is not exact
+    ///         calibration_noref: bool CALIB.NOREF, no external reference clock
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached or the read fails
+    ///
+    /// Example:
+    ///     >>> st = session.read_systick()
+    ///     >>> print(st["enabled"], st["reload"] + 1, "cycles per tick")
+    fn read_systick<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let regs = self.with_core(|core| read_regs(core, self.retry_policy, cortex_m::SYST_CSR, 4))?;
+        let (csr, rvr, cvr, calib) = (regs[0], regs[1], regs[2], regs[3]);
+
+        let dict = PyDict::new(py);
+        dict.set_item("enabled", csr & 1 != 0)?;
+        dict.set_item("interrupt_enabled", csr & (1 << 1) != 0)?;
+        dict.set_item(
+            "clock_source",
+            if csr & (1 << 2) != 0 { "processor" } else { "external" },
+        )?;
+        dict.set_item("count_flag", csr & (1 << 16) != 0)?;
+        dict.set_item("reload", rvr & 0x00FF_FFFF)?;
+        dict.set_item("current", cvr & 0x00FF_FFFF)?;
+        dict.set_item("calibration_tenms", calib & 0x00FF_FFFF)?;
+        dict.set_item("calibration_skew", calib & (1 << 30) != 0)?;
+        dict.set_item("calibration_noref", calib & (1 << 31) != 0)?;
+        Ok(dict)
+    }
+
+    /// Read target memory into a typed buffer for zero-copy numpy access.
+    ///
+    /// Like the read_mem* methods, but returns a `MemoryBuffer` implementing the
+    /// buffer protocol with a declared element type, so captured sample buffers
+    /// (ADC data, telemetry) go straight into numpy without an extra copy.
+    ///
+    /// Args:
+    ///     address: Start address (aligned to the element size)
+    ///     count: Number of elements to read
+    ///     dtype: Element type: "u8" (default), "u16", "u32" or "f32"
+    ///     progress: Optional callable `progress(bytes_read, total_bytes)`, called
+    ///         after every 64 KB chunk. Large reads (full RAM dumps) take seconds;
+    ///         this lets a UI show they are moving. Ctrl-C is checked between chunks.
+    ///
+    /// Returns:
+    ///     MemoryBuffer: Buffer of `count` elements
+    ///
+    /// Raises:
+    ///     ValueError: If dtype is unknown, the address is misaligned or the
+    ///         read is larger than 256 MB
+    ///     RuntimeError: If not attached or the memory read fails
+    ///
+    /// Example:
+    ///     >>> import numpy as np
+    ///     >>> samples = np.asarray(session.read_buffer(0x20001000, 1024, dtype="u16"))
+    ///     >>> ram = session.read_buffer(0x20000000, 256 * 1024,
+    ///     ...                           progress=lambda done, total: print(f"{done}/{total}"))
+    #[pyo3(signature = (address, count, dtype="u8", progress=None))]
+    fn read_buffer(
+        &self,
+        py: Python<'_>,
+        address: u64,
+        count: usize,
+        dtype: &str,
+        progress: Option<PyObject>,
+    ) -> PyResult<MemoryBuffer> {
+        const CHUNK_BYTES: usize = 64 * 1024;
+
+        let dtype = BufferDtype::parse(dtype)?;
+        check_alignment(address, dtype.itemsize() as u32 * 8)?;
+
+        let itemsize = dtype.itemsize();
+        let total = check_read_size(count, itemsize)?;
+        let mut data = Vec::with_capacity(total);
+        while data.len() < total {
+            let chunk_address = address + data.len() as u64;
+            let chunk_count = (total - data.len()).min(CHUNK_BYTES) / itemsize;
+            let chunk = py.allow_threads(|| {
+                self.with_core(|core| {
+                    let result = match dtype {
+                        BufferDtype::U8 => {
+                            let mut bytes = vec![0u8; chunk_count];
+                            retry_transient(self.retry_policy, || core.read_8(chunk_address, &mut bytes)).map(|_| bytes)
+                        }
+                        BufferDtype::U16 => {
+                            let mut values = vec![0u16; chunk_count];
+                            retry_transient(self.retry_policy, || core.read_16(chunk_address, &mut values))
+                                .map(|_| values.iter().flat_map(|v| v.to_ne_bytes()).collect())
+                        }
+                        BufferDtype::U32 | BufferDtype::F32 => {
+                            let mut values = vec![0u32; chunk_count];
+                            retry_transient(self.retry_policy, || core.read_32(chunk_address, &mut values))
+                                .map(|_| values.iter().flat_map(|v| v.to_ne_bytes()).collect())
+                        }
+                    };
+                    result.map_err(|e| {
+                        pyo3::exceptions::PyRuntimeError::new_err(format!(
+                            "Memory read at 0x{:08x} failed: {}",
+                            chunk_address, e
+                        ))
+                    })
+                })
+            })?;
+            data.extend_from_slice(&chunk);
+
+            // Called without the session lock held, so the callback may use the session
+            if let Some(ref progress) = progress {
+                progress.call1(py, (data.len(), total))?;
+            }
+            py.check_signals()?;
+        }
+
+        let itemsize = itemsize as isize;
+        Ok(MemoryBuffer {
+            data,
+            dtype,
+            shape: [count as isize],
+            strides: [itemsize],
+        })
+    }
+
+    /// Snapshot the same memory region repeatedly at a fixed rate.
+    ///
+    /// For sampling a live buffer (e.g. a double-buffered ADC DMA target). Reads
+    /// are scheduled every `interval_ms` from the first one; if a read takes longer
+    /// than the interval, the next one starts immediately. Each snapshot is
+    /// timestamped on the host when its read completes. The GIL is released while
+    /// waiting and reading, and the session lock is only held for each read, so
+    /// RTT readers and other threads keep running in between. Ctrl-C (or another
+    /// pending signal) interrupts the series.
+    ///
+    /// Args:
+    ///     address: Start address of the region
+    ///     length: Region size in bytes
+    ///     count: Number of snapshots to take
+    ///     interval_ms: Time between snapshot starts (0 = back to back)
+    ///
+    /// Returns:
+    ///     list[tuple[float, bytes]]: (Unix timestamp in seconds, region contents)
+    ///     per snapshot, oldest first
+    ///
+    /// Raises:
+    ///     ValueError: If count * interval_ms is too long to schedule, or
+    ///         count * length is more than 256 MB
+    ///     RuntimeError: If not attached or a read fails
+    ///     KeyboardInterrupt: If interrupted (snapshots taken so far are discarded)
+    ///
+    /// Example:
+    ///     >>> snaps = session.read_region_repeated(0x20001000, 512, count=100, interval_ms=10)
+    ///     >>> for t, data in snaps:
+    ///     ...     print(t, data[:4].hex())
+    #[pyo3(signature = (address, length, count, interval_ms))]
+    fn read_region_repeated<'py>(
+        &self,
+        py: Python<'py>,
+        address: u64,
+        length: usize,
+        count: usize,
+        interval_ms: u64,
+    ) -> PyResult<Bound<'py, PyList>> {
+        // Longest pause between signal checks while waiting for the next snapshot
+        const SIGNAL_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+        // Every snapshot is kept until the series ends, so bound the total up front
+        check_read_size(count, length)?;
+        let interval = Duration::from_millis(interval_ms);
+        let start = Instant::now();
+        // Validated up front, so the per-snapshot deadlines below can't overflow
+        u32::try_from(count)
+            .ok()
+            .and_then(|count| interval.checked_mul(count))
+            .and_then(|span| start.checked_add(span))
+            .ok_or_else(|| {
+                pyo3::exceptions::PyValueError::new_err(format!(
+                    "{} snapshots every {} ms is too long a series to schedule",
+                    count, interval_ms
+                ))
+            })?;
+
+        let mut snapshots = Vec::with_capacity(count);
+        for i in 0..count {
+            let due = start + interval * i as u32;
+            while let Some(wait) = due.checked_duration_since(Instant::now()) {
+                if wait.is_zero() {
+                    break;
+                }
+                py.allow_threads(|| std::thread::sleep(wait.min(SIGNAL_CHECK_INTERVAL)));
+                py.check_signals()?;
+            }
+
+            let data = py.allow_threads(|| {
+                self.with_core(|core| {
+                    let mut data = vec![0u8; length];
+                    retry_transient(self.retry_policy, || core.read_8(address, &mut data)).map_err(|e| {
+                        pyo3::exceptions::PyRuntimeError::new_err(format!(
+                            "Memory read at 0x{:08x} failed (snapshot {}): {}",
+                            address, i, e
+                        ))
+                    })?;
+                    Ok(data)
+                })
+            })?;
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs_f64();
+            snapshots.push((timestamp, data));
+            py.check_signals()?;
+        }
+
+        let list = PyList::empty(py);
+        for (timestamp, data) in snapshots {
+            list.append((timestamp, PyBytes::new(py, &data)))?;
+        }
+        Ok(list)
+    }
+
+    /// Read the DWT profiling counters, enabling them first if needed.
+    ///
+    /// The DWT exception/stall counters give a cheap, non-intrusive breakdown of
+    /// where cycles go: CPICNT (extra cycles per instruction), EXCCNT (exception
+    /// entry/exit overhead), SLEEPCNT (cycles asleep), LSUCNT (load/store stalls)
+    /// and FOLDCNT (folded instructions). They are 8-bit and wrap, so sample them
+    /// periodically and accumulate deltas (mod 256). With `enable=True`,
+    /// DEMCR.TRCENA and the counter enable bits in DWT_CTRL are set first; the
+    /// counters only start counting from that point.
+    ///
+    /// Args:
+    ///     enable: Set the enable bits before reading (default True)
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         cyccnt: int DWT_CYCCNT (32-bit; only counts if CYCCNTENA is set)
+    ///         cpicnt, exccnt, sleepcnt, lsucnt, foldcnt: int 8-bit counter values
+    ///
+    /// Raises:
+    ///     NotImplementedError: If the core has no DWT profiling counters (e.g. Cortex-M0)
+    ///     PermissionError: If enable=True on a read-only session
+    ///     RuntimeError: If not attached or an access fails
+    ///
+    /// Example:
+    ///     >>> before = session.read_dwt_counters()
+    ///     >>> after = session.read_dwt_counters()
+    ///     >>> print("exception overhead:", (after["exccnt"] - before["exccnt"]) % 256)
+    #[pyo3(signature = (enable=true))]
+    fn read_dwt_counters<'py>(&self, py: Python<'py>, enable: bool) -> PyResult<Bound<'py, PyDict>> {
+        if enable {
+            self.check_writable("Enabling DWT counters")?;
+        }
+
+        let counters = self.with_core(|core| {
+            let write = |core: &mut Core, address: u64, value: u32| {
+                core.write_word_32(address, value).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Failed to write register at 0x{:08x}: {}",
+                        address, e
+                    ))
+                })
+            };
+
+            if enable {
+                let demcr = read_reg(core, self.retry_policy, cortex_m::DEMCR)?;
+                write(core, cortex_m::DEMCR, demcr | cortex_m::DEMCR_TRCENA)?;
+            }
+
+            let ctrl = read_reg(core, self.retry_policy, cortex_m::DWT_CTRL)?;
+            if ctrl & cortex_m::DWT_CTRL_NOPRFCNT != 0 {
+                return Err(pyo3::exceptions::PyNotImplementedError::new_err(
+                    "DWT profiling counters are not implemented on this core",
+                ));
+            }
+            if enable {
+                write(core, cortex_m::DWT_CTRL, ctrl | cortex_m::DWT_CTRL_PROFILING_ENA)?;
+            }
+
+            read_regs(core, self.retry_policy, cortex_m::DWT_CYCCNT, 6)
+        })?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("cyccnt", counters[0])?;
+        for (name, value) in ["cpicnt", "exccnt", "sleepcnt", "lsucnt", "foldcnt"]
+            .iter()
+            .zip(&counters[1..])
+        {
+            dict.set_item(*name, value & 0xFF)?;
+        }
+        Ok(dict)
+    }
+
+    /// Read and decode DEMCR, the debug exception and monitor control register.
+    ///
+    /// DEMCR.TRCENA (DWTENA on ARMv6-M) gates the DWT and ITM units: SWO, ITM
+    /// stimulus ports, DWT watchpoints and cycle counting all read as dead while
+    /// it is clear. Check this first when trace "isn't working". The vector-catch
+    /// bits show which faults halt the core into the debugger.
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         raw: int raw DEMCR value
+    ///         trace_enabled: bool TRCENA
+    ///         vector_catch: list[str] enabled vector catches, from "core_reset",
+    ///             "mm_err", "nocp_err", "chk_err", "stat_err", "bus_err",
+    ///             "int_err", "hard_err"
+    ///         monitor_enabled: bool MON_EN, DebugMonitor exception enabled
+    ///         monitor_pending: bool MON_PEND
+    ///         monitor_step: bool MON_STEP
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached or the read fails
+    ///
+    /// Example:
+    ///     >>> if not session.read_demcr()["trace_enabled"]:
+    ///     ...     session.set_trace_enable(True)
+    fn read_demcr<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        const VECTOR_CATCH: [(u32, &str); 8] = [
+            (0, "core_reset"),
+            (4, "mm_err"),
+            (5, "nocp_err"),
+            (6, "chk_err"),
+            (7, "stat_err"),
+            (8, "bus_err"),
+            (9, "int_err"),
+            (10, "hard_err"),
+        ];
+
+        let raw = self.with_core(|core| read_reg(core, self.retry_policy, cortex_m::DEMCR))?;
+        let vector_catch: Vec<&str> = VECTOR_CATCH
+            .iter()
+            .filter(|(bit, _)| raw & (1 << bit) != 0)
+            .map(|&(_, name)| name)
+            .collect();
+
+        let dict = PyDict::new(py);
+        dict.set_item("raw", raw)?;
+        dict.set_item("trace_enabled", raw & cortex_m::DEMCR_TRCENA != 0)?;
+        dict.set_item("vector_catch", vector_catch)?;
+        dict.set_item("monitor_enabled", raw & (1 << 16) != 0)?;
+        dict.set_item("monitor_pending", raw & (1 << 17) != 0)?;
+        dict.set_item("monitor_step", raw & (1 << 18) != 0)?;
+        Ok(dict)
+    }
+
+    /// Set or clear DEMCR.TRCENA, the global enable for DWT and ITM.
+    ///
+    /// Read-modify-write, so the vector-catch and monitor bits are preserved. The
+    /// bit is read back afterwards: on a core without DWT/ITM it may not stick.
+    ///
+    /// Args:
+    ///     on: True to enable trace, False to disable it
+    ///
+    /// Returns:
+    ///     bool: Previous TRCENA state, for restoring it later
+    ///
+    /// Raises:
+    ///     PermissionError: If the session is read-only
+    ///     RuntimeError: If not attached, an access fails, or the bit doesn't
+    ///         take the requested value
+    ///
+    /// Example:
+    ///     >>> was_on = session.set_trace_enable(True)
+    ///     >>> ...  # use DWT / ITM
+    ///     >>> session.set_trace_enable(was_on)
+    fn set_trace_enable(&self, on: bool) -> PyResult<bool> {
+        self.check_writable("set_trace_enable()")?;
+        self.with_core(|core| {
+            let demcr = read_reg(core, self.retry_policy, cortex_m::DEMCR)?;
+            let value = if on {
+                demcr | cortex_m::DEMCR_TRCENA
+            } else {
+                demcr & !cortex_m::DEMCR_TRCENA
+            };
+            core.write_word_32(cortex_m::DEMCR, value).map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to write DEMCR: {}", e))
+            })?;
+
+            let readback = read_reg(core, self.retry_policy, cortex_m::DEMCR)?;
+            if (readback & cortex_m::DEMCR_TRCENA != 0) != on {
+                return Err(pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "DEMCR.TRCENA did not {} (DEMCR = 0x{:08x}); the core may not implement DWT/ITM",
+                    if on { "set" } else { "clear" },
+                    readback
+                )));
+            }
+            Ok(demcr & cortex_m::DEMCR_TRCENA != 0)
+        })
+    }
+
+    /// Read the panic/assert message the firmware left in a persistent RAM buffer.
+    ///
+    /// Many frameworks copy the last panic or assert message into a char array in
+    /// a noinit section, which survives a reset. When the device crashes and
+    /// reboots before the host can drain RTT, this buffer is often the only record
+    /// of what went wrong. The buffer is located by address, or by symbol name in
+    /// the firmware's ELF, and read as a NUL-terminated string. The symbol must be
+    /// the array itself, not a pointer to it.
+    ///
+    /// A noinit buffer holds random data after a power-on reset, so firmware
+    /// usually clears it (or guards it with a magic word) once the message has been
+    /// reported; this method only reads it.
+    ///
+    /// Args:
+    ///     address: Address of the message buffer (takes precedence)
+    ///     elf_path: Path to the ELF file, used with `symbol`
+    ///     symbol: Symbol name of the message buffer (e.g. "panic_msg")
+    ///     max_len: Maximum number of bytes to read (default 256)
+    ///
+    /// Returns:
+    ///     str | None: The message (invalid UTF-8 replaced), or None if the buffer
+    ///         is empty
+    ///
+    /// Raises:
+    ///     ValueError: If neither address nor elf_path and symbol are given, or the
+    ///         symbol is not found
+    ///     RuntimeError: If not attached or the memory read fails
+    ///
+    /// Example:
+    ///     >>> session.attach()
+    ///     >>> msg = session.read_panic_message(elf_path="build/zephyr/zephyr.elf", symbol="panic_msg")
+    ///     >>> if msg:
+    ///     ...     print("Last panic:", msg)
+    #[pyo3(signature = (address=None, elf_path=None, symbol=None, max_len=256))]
+    fn read_panic_message(
+        &self,
+        address: Option<u64>,
+        elf_path: Option<String>,
+        symbol: Option<String>,
+        max_len: usize,
+    ) -> PyResult<Option<String>> {
+        let address = match (address, elf_path, symbol) {
+            (Some(addr), _, _) => addr,
+            (None, Some(elf), Some(symbol)) => require_symbol(&elf, &symbol)?,
+            _ => {
+                return Err(pyo3::exceptions::PyValueError::new_err(
+                    "Provide address, or elf_path and symbol",
+                ))
+            }
+        };
+
+        let bytes = self.with_core(|core| read_cstring(core, self.retry_policy, address, max_len))?;
+        if bytes.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(String::from_utf8_lossy(&bytes).to_string()))
+    }
+
+    /// Report whether the core is executing from flash or RAM.
+    ///
+    /// Reads the PC and classifies it against the target's memory map, e.g. to
+    /// tell whether a bootloader has jumped to the application or is still
+    /// running in the loader (or executing a RAM-resident routine). Reading the PC
+    /// requires a halt: a running core is halted briefly and resumed afterwards,
+    /// a halted core is left as it is. Read-only sessions never halt the core, so
+    /// there the core must already be halted.
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         pc: int program counter
+    ///         region: str "flash", "ram" or "unknown" (a region that is neither,
+    ///             e.g. boot ROM, or an address outside the memory map)
+    ///         region_name: str | None name of the memory-map region, if it has one
+    ///         was_running: bool True if the core was halted for the read
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached, or halting or reading the PC fails
+    ///     PermissionError: If the core is running on a read-only session
+    ///
+    /// Example:
+    ///     >>> session.execution_region()["region"]
+    ///     'flash'
+    fn execution_region<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let memory_map = self.memory_map()?;
+        let (pc, was_running) = self.with_core(|core| {
+            let was_running = !core.core_halted().map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to read core status: {}", e))
+            })?;
+            if was_running {
+                self.check_writable("execution_region() on a running core")?;
+                core.halt(Duration::from_millis(100)).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!("Halt failed: {}", e))
+                })?;
+            }
+
+            let pc_register = core.program_counter();
+            let pc: PyResult<u64> = core.read_core_reg(pc_register).map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to read PC: {}", e))
+            });
+
+            // Resume even if the PC read failed, so a failed query doesn't leave the
+            // target stopped
+            if was_running {
+                core.run().map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to resume core: {}", e))
+                })?;
+            }
+            Ok((pc?, was_running))
+        })?;
+
+        let (region, region_name) = classify_address(&memory_map, pc);
+
+        let dict = PyDict::new(py);
+        dict.set_item("pc", pc)?;
+        dict.set_item("region", region)?;
+        dict.set_item("region_name", region_name)?;
+        dict.set_item("was_running", was_running)?;
+        Ok(dict)
+    }
+
+    /// Read the Vector Table Offset Register (VTOR, 0xE000ED08).
+    ///
+    /// Shows which vector table is live: a bootloader runs with its own table and
+    /// the application is expected to point VTOR at its table early in startup.
+    /// If VTOR still points into the bootloader after the jump, interrupts will
+    /// be dispatched to the loader's handlers. The address is classified against
+    /// the target's memory map. No halt is needed.
+    ///
+    /// Cortex-M0 has no VTOR (the table is fixed at 0); the register reads as 0
+    /// there, or the read fails.
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         vtor: int vector table address
+    ///         region: str "flash", "ram" or "unknown"
+    ///         region_name: str | None name of the memory-map region, if it has one
+    ///         initial_sp: int first table entry (initial stack pointer)
+    ///         reset_handler: int second table entry (Reset_Handler, Thumb bit set)
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached or a read fails
+    ///
+    /// Example:
+    ///     >>> vtor = session.read_vtor()
+    ///     >>> print(f"vector table at {vtor['vtor']:#x} ({vtor['region']})")
+    fn read_vtor<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let memory_map = self.memory_map()?;
+        let (vtor, entries) = self.with_core(|core| {
+            let vtor = read_reg(core, self.retry_policy, cortex_m::VTOR)?;
+            Ok((vtor, read_regs(core, self.retry_policy, u64::from(vtor), 2)?))
+        })?;
+        let (region, region_name) = classify_address(&memory_map, u64::from(vtor));
+
+        let dict = PyDict::new(py);
+        dict.set_item("vtor", vtor)?;
+        dict.set_item("region", region)?;
+        dict.set_item("region_name", region_name)?;
+        dict.set_item("initial_sp", entries[0])?;
+        dict.set_item("reset_handler", entries[1])?;
+        Ok(dict)
+    }
+
+    /// Sanity-check the vector table the target boots from.
+    ///
+    /// A blank or corrupt image gives no error: the core just never runs. This
+    /// reads the first two table entries and checks that the initial stack
+    /// pointer points into RAM and the reset handler into flash with the Thumb bit
+    /// set. The stack check accepts a stack pointer one past the end of a RAM
+    /// region, since the stack usually starts at the top of RAM. Images built to run
+    /// from RAM fail the flash check by design.
+    ///
+    /// Args:
+    ///     address: Vector table address (default: start of the first flash region
+    ///         in the memory map, where the boot table normally lives)
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         address: int vector table address checked
+    ///         initial_sp: int first table entry
+    ///         reset_handler: int second table entry
+    ///         initial_sp_in_ram: bool check result
+    ///         reset_handler_in_flash: bool check result
+    ///         reset_handler_thumb: bool check result (bit 0 set)
+    ///         blank: bool both entries are erased (0xFFFFFFFF) or zero
+    ///         valid: bool all checks passed
+    ///
+    /// Raises:
+    ///     ValueError: If no address is given and the memory map has no flash region
+    ///     RuntimeError: If not attached or the read fails
+    ///
+    /// Example:
+    ///     >>> check = session.validate_image()
+    ///     >>> if not check["valid"]:
+    ///     ...     print("image looks blank" if check["blank"] else check)
+    #[pyo3(signature = (address=None))]
+    fn validate_image<'py>(&self, py: Python<'py>, address: Option<u64>) -> PyResult<Bound<'py, PyDict>> {
+        let memory_map = self.memory_map()?;
+        let address = match address {
+            Some(address) => address,
+            None => memory_map
+                .iter()
+                .find_map(|region| match region {
+                    MemoryRegion::Nvm(nvm) => Some(nvm.range.start),
+                    _ => None,
+                })
+                .ok_or_else(|| {
+                    pyo3::exceptions::PyValueError::new_err(format!(
+                        "The probe-rs memory map for '{}' has no flash region; pass address=",
+                        self.chip
+                    ))
+                })?,
+        };
+        let entries = self.with_core(|core| read_regs(core, self.retry_policy, address, 2))?;
+        let (initial_sp, reset_handler) = (entries[0], entries[1]);
+        let sp_in_ram = initial_sp != 0
+            && classify_address(&memory_map, u64::from(initial_sp) - 1).0 == "ram";
+        let handler_in_flash = classify_address(&memory_map, u64::from(reset_handler & !1)).0 == "flash";
+        let handler_thumb = reset_handler & 1 != 0;
+        let blank = (initial_sp == 0xFFFF_FFFF && reset_handler == 0xFFFF_FFFF)
+            || (initial_sp == 0 && reset_handler == 0);
+
+        let dict = PyDict::new(py);
+        dict.set_item("address", address)?;
+        dict.set_item("initial_sp", initial_sp)?;
+        dict.set_item("reset_handler", reset_handler)?;
+        dict.set_item("initial_sp_in_ram", sp_in_ram)?;
+        dict.set_item("reset_handler_in_flash", handler_in_flash)?;
+        dict.set_item("reset_handler_thumb", handler_thumb)?;
+        dict.set_item("blank", blank)?;
+        dict.set_item("valid", sp_in_ram && handler_in_flash && handler_thumb)?;
+        Ok(dict)
+    }
+
+    /// Read the build hash string the firmware embeds for traceability.
+    ///
+    /// Many teams compile the git commit or a build id into the image as a
+    /// NUL-terminated char array. This looks the symbol up in the ELF and reads
+    /// the string back from the device, giving a human-readable answer to "which
+    /// build is running?" that can go straight into a test log. Because it is read
+    /// from the target, a device flashed with a different build reports its own
+    /// hash (or garbage), not the ELF's.
+    ///
+    /// Args:
+    ///     elf_path: Path to the ELF file the running firmware was built from
+    ///     symbol: Symbol of the char array. If omitted, "git_sha" and then
+    ///         "__build_id__" are tried
+    ///     max_len: Maximum number of bytes to read (default 64)
+    ///
+    /// Returns:
+    ///     str | None: The build hash (invalid UTF-8 replaced), or None if empty
+    ///
+    /// Raises:
+    ///     ValueError: If the symbol (or none of the defaults) is in the ELF
+    ///     RuntimeError: If not attached or the memory read fails
+    ///
+    /// Example:
+    ///     >>> print("device build:", session.read_build_hash("build/zephyr/zephyr.elf"))
+    #[pyo3(signature = (elf_path, symbol=None, max_len=64))]
+    fn read_build_hash(&self, elf_path: &str, symbol: Option<String>, max_len: usize) -> PyResult<Option<String>> {
+        const DEFAULT_SYMBOLS: [&str; 2] = ["git_sha", "__build_id__"];
+
+        let address = match symbol {
+            Some(symbol) => require_symbol(elf_path, &symbol)?,
+            None => {
+                let mut address = None;
+                for name in DEFAULT_SYMBOLS {
+                    if let Some(addr) = find_symbol(elf_path, name)? {
+                        address = Some(addr);
+                        break;
+                    }
+                }
+                address.ok_or_else(|| {
+                    pyo3::exceptions::PyValueError::new_err(format!(
+                        "None of the build hash symbols {:?} found in ELF file '{}'",
+                        DEFAULT_SYMBOLS, elf_path
+                    ))
+                })?
+            }
+        };
+
+        let bytes = self.with_core(|core| read_cstring(core, self.retry_policy, address, max_len))?;
+        if bytes.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(String::from_utf8_lossy(&bytes).to_string()))
+    }
+
+    /// Read the firmware's heap-free and stack high-water-mark globals.
+    ///
+    /// RTOSes and allocators usually keep these in globals (e.g. a heap's free-byte
+    /// counter, or a high-water mark updated by a stack checker). This reads them
+    /// as 32-bit values in one go, for a memory-health view. At least one symbol
+    /// must be given. With `percent_of_ram`, each value is also expressed as a
+    /// percentage of the target's total RAM (sum of the RAM regions in the
+    /// probe-rs memory map).
+    ///
+    /// Args:
+    ///     elf_path: Path to the ELF file the running firmware was built from
+    ///     heap_free_symbol: Symbol holding the free heap bytes (optional)
+    ///     stack_watermark_symbol: Symbol holding the stack high-water mark (optional)
+    ///     percent_of_ram: Add RAM totals and percentages (default False)
+    ///
+    /// Returns:
+    ///     dict with keys:
+    ///         heap_free: int value, or None if no heap symbol was given
+    ///         stack_watermark: int value, or None if no stack symbol was given
+    ///         ram_total: int total RAM bytes (only with percent_of_ram)
+    ///         heap_free_percent, stack_watermark_percent: float percentage of
+    ///             ram_total, or None (only with percent_of_ram)
+    ///
+    /// Raises:
+    ///     ValueError: If no symbol is given, a symbol is not in the ELF, or
+    ///         percent_of_ram is set but the memory map has no RAM
+    ///     RuntimeError: If not attached or a memory read fails
+    ///
+    /// Example:
+    ///     >>> usage = session.read_memory_usage("build/zephyr/zephyr.elf",
+    ///     ...     heap_free_symbol="heap_free_bytes", percent_of_ram=True)
+    ///     >>> print(f"{usage['heap_free_percent']:.1f}% of RAM free in heap")
+    #[pyo3(signature = (elf_path, heap_free_symbol=None, stack_watermark_symbol=None, percent_of_ram=false))]
+    fn read_memory_usage<'py>(
+        &self,
+        py: Python<'py>,
+        elf_path: &str,
+        heap_free_symbol: Option<&str>,
+        stack_watermark_symbol: Option<&str>,
+        percent_of_ram: bool,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let names: Vec<&str> = heap_free_symbol.iter().chain(&stack_watermark_symbol).copied().collect();
+        if names.is_empty() {
+            return Err(pyo3::exceptions::PyValueError::new_err(
+                "Give heap_free_symbol, stack_watermark_symbol or both",
+            ));
+        }
+        let addresses = find_symbols(elf_path, &names)?;
+
+        let ram_total: u64 = {
+            let session_guard = self.session.lock().unwrap();
+            let session = session_guard
+                .as_ref()
+                .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Not attached"))?;
+            session
+                .target()
+                .memory_map
+                .iter()
+                .filter_map(|region| match region {
+                    MemoryRegion::Ram(ram) => Some(ram.range.end - ram.range.start),
+                    _ => None,
+                })
+                .sum()
+        };
+        if percent_of_ram && ram_total == 0 {
+            return Err(pyo3::exceptions::PyValueError::new_err(format!(
+                "The probe-rs memory map for '{}' has no RAM regions to compute percentages against",
+                self.chip
+            )));
+        }
+
+        let read_symbol = |symbol: Option<&str>| -> PyResult<Option<u32>> {
+            let Some(symbol) = symbol else {
+                return Ok(None);
+            };
+            let address = addresses[symbol];
+            self.with_core(|core| {
+                retry_transient(self.retry_policy, || core.read_word_32(address)).map_err(|e| {
+                    pyo3::exceptions::PyRuntimeError::new_err(format!(
+                        "Failed to read '{}' at 0x{:08x}: {}",
+                        symbol, address, e
+                    ))
+                })
+            })
+            .map(Some)
+        };
+        let heap_free = read_symbol(heap_free_symbol)?;
+        let stack_watermark = read_symbol(stack_watermark_symbol)?;
+
+        let dict = PyDict::new(py);
+        dict.set_item("heap_free", heap_free)?;
+        dict.set_item("stack_watermark", stack_watermark)?;
+        if percent_of_ram {
+            let percent = |value: Option<u32>| value.map(|v| f64::from(v) * 100.0 / ram_total as f64);
+            dict.set_item("ram_total", ram_total)?;
+            dict.set_item("heap_free_percent", percent(heap_free))?;
+            dict.set_item("stack_watermark_percent", percent(stack_watermark))?;
+        }
+        Ok(dict)
+    }
+
+    /// Read an integer global by symbol name, with an explicit type.
+    ///
+    /// Companion to write_symbol_int(), e.g. for reading the firmware's active
+    /// log level before changing it.
+    ///
+    /// Args:
+    ///     elf_path: Path to the ELF file the running firmware was built from
+    ///     symbol: Symbol name of the global (e.g. "log_level")
+    ///     dtype: Integer type: "u8", "i8", "u16", "i16", "u32" (default), "i32",
+    ///         "u64" or "i64"
+    ///
+    /// Returns:
+    ///     int: Current value of the variable
+    ///
+    /// Raises:
+    ///     ValueError: If the type is not an integer type or the symbol is not found
+    ///     RuntimeError: If not attached or the memory read fails
+    ///
+    /// Example:
+    ///     >>> level = session.read_symbol_int("build/zephyr/zephyr.elf", "log_level", dtype="u8")
+    #[pyo3(signature = (elf_path, symbol, dtype="u32"))]
+    fn read_symbol_int<'py>(
+        &self,
+        py: Python<'py>,
+        elf_path: &str,
+        symbol: &str,
+        dtype: &str,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let value_type = ValueType::parse(dtype)?;
+        value_type.require_int()?;
+        let address = require_symbol(elf_path, symbol)?;
+
+        let bytes = self.with_core(|core| {
+            let mut bytes = vec![0u8; value_type.size()];
+            retry_transient(self.retry_policy, || core.read_8(address, &mut bytes)).map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "Failed to read '{}' at 0x{:08x}: {}",
+                    symbol, address, e
+                ))
+            })?;
+            Ok(bytes)
+        })?;
+        value_type.decode(py, &bytes)
+    }
+
+    /// Write an integer global by symbol name, e.g. to change the log level at runtime.
+    ///
+    /// Turns a build-time setting into a runtime knob without reflashing, but only
+    /// if the firmware reads the variable each time it is used. A `const` or a
+    /// value the compiler folded into the code won't change behavior; declare it as
+    /// a mutable (ideally `volatile`) global in RAM. The value is range-checked
+    /// against the type before anything is written.
+    ///
+    /// Args:
+    ///     elf_path: Path to the ELF file the running firmware was built from
+    ///     symbol: Symbol name of the global (e.g. "log_level")
+    ///     value: New value
+    ///     dtype: Integer type of the variable, as for read_symbol_int() (default "u32")
+    ///
+    /// Raises:
+    ///     ValueError: If the type is not an integer type, the value doesn't fit,
+    ///         or the symbol is not found
+    ///     PermissionError: If the session is read-only
+    ///     RuntimeError: If not attached or the memory write fails
+    ///
+    /// Example:
+    ///     >>> session.write_symbol_int("build/zephyr/zephyr.elf", "log_level", 4, dtype="u8")
+    #[pyo3(signature = (elf_path, symbol, value, dtype="u32"))]
+    fn write_symbol_int(&self, elf_path: &str, symbol: &str, value: i128, dtype: &str) -> PyResult<()> {
+        self.check_writable("write_symbol_int()")?;
+        let bytes = ValueType::parse(dtype)?.encode_int(value)?;
+        let address = require_symbol(elf_path, symbol)?;
+
+        self.with_core(|core| {
+            retry_transient(self.retry_policy, || core.write_8(address, &bytes)).map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "Failed to write '{}' at 0x{:08x}: {}",
+                    symbol, address, e
+                ))
+            })
+        })
+    }
+
+    /// Read a 32-bit global variable by symbol name.
+    ///
+    /// Resolves the symbol address from the ELF and reads its current value from
+    /// target memory. Works while the core is running.
+    ///
+    /// Args:
+    ///     elf_path: Path to the ELF file the running firmware was built from
+    ///     symbol: Symbol name of the global (e.g., "uptime_ms")
+    ///
+    /// Returns:
+    ///     int: Current value of the variable
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached or the memory read fails
+    ///     ValueError: If the symbol is not found in the ELF
+    ///
+    /// Example:
+    ///     >>> ticks = session.read_symbol_u32("build/zephyr/zephyr.elf", "uptime_ms")
+    fn read_symbol_u32(&self, elf_path: &str, symbol: &str) -> PyResult<u32> {
+        let address = require_symbol(elf_path, symbol)?;
+        self.with_core(|core| {
+            retry_transient(self.retry_policy, || core.read_word_32(address)).map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "Failed to read '{}' at 0x{:08x}: {}",
+                    symbol, address, e
+                ))
+            })
+        })
+    }
+
+    /// Read a 64-bit global variable by symbol name.
+    ///
+    /// Same as `read_symbol_u32()` but reads 8 bytes (e.g., a 64-bit uptime counter).
+    ///
+    /// Args:
+    ///     elf_path: Path to the ELF file the running firmware was built from
+    ///     symbol: Symbol name of the global (e.g., "uptime_us")
+    ///
+    /// Returns:
+    ///     int: Current value of the variable
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached or the memory read fails
+    ///     ValueError: If the symbol is not found in the ELF
+    ///
+    /// Example:
+    ///     >>> uptime_us = session.read_symbol_u64("build/zephyr/zephyr.elf", "uptime_us")
+    fn read_symbol_u64(&self, elf_path: &str, symbol: &str) -> PyResult<u64> {
+        let address = require_symbol(elf_path, symbol)?;
+        self.with_core(|core| {
+            retry_transient(self.retry_policy, || core.read_word_64(address)).map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!(
+                    "Failed to read '{}' at 0x{:08x}: {}",
+                    symbol, address, e
+                ))
+            })
+        })
+    }
+
+    /// Cheap liveness check for heartbeat/health indicators.
+    ///
+    /// Does a single read of DHCSR and reports whether it succeeded. Unlike the
+    /// other methods this never raises: not being attached, a probe error or a
+    /// transient glitch all simply return False.
+    ///
+    /// Returns:
+    ///     bool: True if the target answered the debug read
+    ///
+    /// Example:
+    ///     >>> if not session.ping():
+    ///     ...     print("Target not responding")
+    fn ping(&self) -> bool {
+        let mut session_guard = self.session.lock().unwrap();
+        let Some(session) = session_guard.as_mut() else {
+            return false;
+        };
+        match session.core(0) {
+            Ok(mut core) => core.read_word_32(cortex_m::DHCSR).is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    /// Run state of every core on the target, in one call.
+    ///
+    /// The other methods only ever touch core 0. On multi-core parts (e.g. the
+    /// nRF5340 application and network cores) this shows, for example, that one
+    /// core is halted in a fault while the other keeps running. A core that can't
+    /// be reached (powered down, held in reset, secured) is reported as
+    /// "unavailable" instead of failing the whole call.
+    ///
+    /// Returns:
+    ///     dict[int, str]: Core index → "running", "halted", "sleeping",
+    ///     "locked_up", "unknown" or "unavailable"
+    ///
+    /// Raises:
+    ///     RuntimeError: If not attached
+    ///     ProbeDisconnectedError: If the probe was unplugged
+    ///
+    /// Example:
+    ///     >>> session.all_cores_status()
+    ///     {0: 'running', 1: 'halted'}
+    fn all_cores_status(&self) -> PyResult<HashMap<usize, String>> {
+        self.check_connected()?;
+        let mut session_guard = self.session.lock().unwrap();
+        let session = session_guard
+            .as_mut()
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Not attached"))?;
+
+        let cores: Vec<usize> = session.list_cores().into_iter().map(|(index, _)| index).collect();
+        let mut statuses = HashMap::new();
+        for index in cores {
+            let status = match session.core(index).and_then(|mut core| core.status()) {
+                Ok(CoreStatus::Running) => "running",
+                Ok(CoreStatus::Halted(_)) => "halted",
+                Ok(CoreStatus::Sleeping) => "sleeping",
+                Ok(CoreStatus::LockedUp) => "locked_up",
+                Ok(CoreStatus::Unknown) => "unknown",
+                Err(_) => "unavailable",
+            };
+            statuses.insert(index, status.to_string());
+        }
+        Ok(statuses)
+    }
+
+    /// Detach from the target and close the probe connection.
+    ///
+    /// Always call this when done to release the probe for other tools.
+    ///
+    /// Example:
+    ///     >>> session.detach()
+    fn detach(&self) -> PyResult<()> {
+        *self.session.lock().unwrap() = None;
+        *self.rtt.lock().unwrap() = None;
+        *self.attached_at.lock().unwrap() = None;
+        Ok(())
+    }
+
+    /// Check if RTT reads are paused (see `pause_rtt()`).
+    ///
+    /// Returns:
+    ///     bool: True if paused
+    #[getter]
+    fn is_rtt_paused(&self) -> bool {
+        self.rtt_paused.load(Ordering::Relaxed)
+    }
+
+    /// Get the chip name this session is configured for.
+    ///
+    /// Returns:
+    ///     str: Chip name
+    #[getter]
+    fn chip(&self) -> String {
+        self.chip.clone()
+    }
+
+    /// Serial number of the probe selected by the last attach().
+    ///
+    /// Unlike `probe_selector` (what the user asked for), this is the physical probe
+    /// that was actually used — including when no selector was given and the first
+    /// probe was picked.
+    ///
+    /// Returns:
+    ///     str | None: Serial number, or None if not attached yet or the probe has none
+    #[getter]
+    fn selected_probe_serial(&self) -> Option<String> {
+        self.selected_probe
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|p| p.serial_number.clone())
+    }
+
+    /// Identifier (product name) of the probe selected by the last attach().
+    ///
+    /// Returns:
+    ///     str | None: Probe identifier (e.g. "STLink V2-1"), or None if not attached yet
+    #[getter]
+    fn selected_probe_identifier(&self) -> Option<String> {
+        self.selected_probe
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|p| p.identifier.clone())
+    }
+
+    /// Check if this session refuses writes and resets (constructor `read_only`).
+    ///
+    /// Returns:
+    ///     bool: True if read-only
+    #[getter]
+    fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Check if the RTT control block was seen to change since start_rtt().
+    ///
+    /// Set by check_rtt_valid() and by RTT reads that fail because the block was
+    /// rebuilt (e.g. after a target reset). Cleared by start_rtt() or a resync.
+    ///
+    /// Returns:
+    ///     bool: True if a desync was detected
+    #[getter]
+    fn rtt_desync_detected(&self) -> bool {
+        self.rtt_desync.load(Ordering::Relaxed)
+    }
+
+    /// Check if currently attached to a target.
+    ///
+    /// Returns:
+    ///     bool: True if attached
+    #[getter]
+    fn is_attached(&self) -> bool {
+        self.session.lock().unwrap().is_some()
+    }
+
+    /// Check if RTT is active.
+    ///
+    /// Returns:
+    ///     bool: True if RTT started
+    #[getter]
+    fn is_rtt_active(&self) -> bool {
+        self.rtt.lock().unwrap().is_some()
+    }
+}
+
+impl ProbeRsSession {
+    /// Forget the per-channel read state of the previous RTT attachment: statistics
+    /// for check_rtt_health() and partial frames for rtt_read_frames().
+    fn reset_channel_state(&self) {
+        self.rtt_stats.lock().unwrap().clear();
+        self.partial_frames.lock().unwrap().clear();
+    }
+
+    /// Read whatever is available on an up channel, recording it in the history.
+    ///
+    /// Shared implementation of the read methods; returns the raw bytes read.
+    fn read_channel(&self, channel: usize) -> PyResult<Vec<u8>> {
+        if self.rtt_paused.load(Ordering::Relaxed) {
+            return Ok(Vec::new());
+        }
+        self.check_connected()?;
+        self.reconnect_if_due()?;
+
+        let chunk_size = self.rtt_tuning.lock().unwrap().read_chunk_size;
+        let (buffer, fill, buffer_size) = self.with_rtt(|core, rtt| {
+            let block = u64::from(rtt.ptr());
+            let num_up = rtt.up_channels().len();
+
+            // Get the up channel (up_channels returns a mutable slice)
+            let up_channel = rtt
+                .up_channels()
+                .get_mut(channel)
+                .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("Channel {} not found", channel)))?;
+
+            // Read up to the tuned chunk size (4KB by default) at a time
+            let mut buffer = vec![0u8; chunk_size];
+            let buffer_size = up_channel.buffer_size();
+            let number = up_channel.number();
+            let count = match up_channel.read(core, &mut buffer) {
+                Ok(count) => count,
+                Err(e) => {
+                    // with_rtt() turns any error into ProbeDisconnectedError if the
+                    // probe is gone, so there's no control block to check then
+                    if core.core_halted().is_err_and(|err| is_disconnect(&err)) {
+                        return Err(pyo3::exceptions::PyRuntimeError::new_err(format!("RTT read failed: {}", e)));
+                    }
+                    // A failed read is the usual symptom of the firmware having reset
+                    // and rebuilt the control block underneath us
+                    if !rtt_block_intact(core, self.retry_policy, block, num_up).unwrap_or(false) {
+                        self.rtt_desync.store(true, Ordering::Relaxed);
+                        return Err(RttError::new_err(format!(
+                            "RTT read failed: {}. The control block at 0x{:08x} has changed \
+                             (target reset?); call check_rtt_valid(resync=True) or start_rtt() again.",
+                            e, block
+                        )));
+                    }
+                    return Err(pyo3::exceptions::PyRuntimeError::new_err(format!("RTT read failed: {}", e)));
+                }
+            };
+
+            buffer.truncate(count);
+            let fill = rtt_read_fill(core, self.retry_policy, block, number, count, chunk_size, buffer_size);
+            Ok((buffer, fill, buffer_size))
+        })?;
+
+        self.record_read(channel, &buffer, fill, buffer_size)?;
+
+        Ok(buffer)
+    }
+
+    /// Reopen the probe and reattach if the session is older than `max_session`.
+    ///
+    /// RTT is reattached at the control block it was using, so channel state is
+    /// preserved. If reopening the probe fails the session is left detached and the
+    /// error is returned; a later attach() starts over. If only the RTT reattach
+    /// fails (e.g. the firmware is mid-boot), the new session is kept with RTT
+    /// stopped, so start_rtt() or wait_for_rtt() can pick it up again.
+    fn reconnect_if_due(&self) -> PyResult<()> {
+        let Some(max_session) = self.max_session else {
+            return Ok(());
+        };
+        let mut attached_at = self.attached_at.lock().unwrap();
+        if !attached_at.is_some_and(|at| at.elapsed() >= max_session) {
+            return Ok(());
+        }
+        let Some(probe_info) = self.selected_probe.lock().unwrap().clone() else {
+            return Ok(());
+        };
+
+        let mut session_guard = self.session.lock().unwrap();
+        let mut rtt_guard = self.rtt.lock().unwrap();
+        let rtt_address = rtt_guard.as_ref().map(|rtt| u64::from(rtt.ptr()));
+
+        // The probe has to be closed before the same USB device can be reopened
+        *rtt_guard = None;
+        *session_guard = None;
+        *attached_at = None;
+
+        let session = self.open_and_attach(&probe_info, false, false).map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!("Scheduled reconnect failed: {}", e))
+        })?;
+        let session = session_guard.insert(session);
+        *attached_at = Some(Instant::now());
+
+        if let Some(address) = rtt_address {
+            let rtt = match session.core(0) {
+                Ok(mut core) => Rtt::attach_at(&mut core, address).map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            match rtt {
+                Ok(rtt) => *rtt_guard = Some(rtt),
+                Err(e) => {
+                    return Err(RttError::new_err(format!(
+                        "Probe reopened by the scheduled reconnect, but RTT did not reattach at \
+                         0x{:08x}: {}. Call start_rtt() or wait_for_rtt() to restart it.",
+                        address, e
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Host-side bookkeeping for data just read from an up channel: statistics,
+    /// history and capture log. Every read path must call this exactly once per read.
+    ///
+    /// `buffer_size` is the target-side size of the channel's ring buffer, and
+    /// `fill` how many bytes it held when the read started (see `rtt_read_fill()`).
+    fn record_read(&self, channel: usize, data: &[u8], fill: usize, buffer_size: usize) -> PyResult<()> {
+        self.rtt_stats
+            .lock()
+            .unwrap()
+            .entry(channel)
+            .or_insert_with(ChannelStats::new)
+            .record(data.len(), fill, buffer_size);
+
+        if let Some(history) = self.history.lock().unwrap().get_mut(&channel) {
+            history.push(data);
+        }
+
+        if !data.is_empty() {
+            if let Some(log) = self.capture_log.lock().unwrap().as_mut() {
+                log.write_record(channel, data).map_err(|e| {
+                    pyo3::exceptions::PyIOError::new_err(format!("Failed to write capture log: {}", e))
+                })?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Refuse `operation` on a read-only session with a PermissionError.
+    fn check_writable(&self, operation: &str) -> PyResult<()> {
+        if self.read_only {
+            return Err(pyo3::exceptions::PyPermissionError::new_err(format!(
+                "{} is not allowed: session was created with read_only=True",
+                operation
+            )));
+        }
+        Ok(())
+    }
+
+    /// Memory map of the attached target.
+    ///
+    /// Copied out under a short lock, so the caller can classify addresses while
+    /// the core access itself goes through `with_core`.
+    fn memory_map(&self) -> PyResult<Vec<MemoryRegion>> {
+        self.check_connected()?;
+        let session_guard = self.session.lock().unwrap();
+        let session = session_guard
+            .as_ref()
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Not attached"))?;
+        Ok(session.target().memory_map.clone())
+    }
+
+    /// Chip series of the configured chip, for the family-aware register methods.
+    fn family(&self) -> PyResult<Family> {
+        Family::from_chip(&self.chip).ok_or_else(|| {
+            pyo3::exceptions::PyNotImplementedError::new_err(format!(
+                "No register map for chip '{}'",
+                self.chip
+            ))
+        })
+    }
+
+    /// Error for a family-aware operation that `family` has no register map for.
+    fn unsupported_family(&self, family: Family, operation: &str) -> PyErr {
+        pyo3::exceptions::PyNotImplementedError::new_err(format!(
+            "{} is not supported for {} (chip '{}')",
+            operation,
+            family.name(),
+            self.chip
+        ))
+    }
 
-    /// Attach to the target chip via a debug probe.
-    ///
-    /// This:
-    /// 1. Lists available debug probes
-    /// 2. Opens the first probe (or the one matching probe_selector)
-    /// 3. Attaches to the target chip via SWD
-    /// 4. Halts the core briefly to establish connection, then resumes
+    /// Register the custom target description, if one was given to the constructor.
     ///
-    /// Raises:
-    ///     RuntimeError: If no probe found, chip not recognized, or connection fails
+    /// probe-rs replaces an already-registered family of the same name, so calling
+    /// this on every attach is harmless.
+    fn register_target_yaml(&self) -> PyResult<()> {
+        let Some(ref path) = self.target_yaml else {
+            return Ok(());
+        };
+
+        let file = fs::File::open(path).map_err(|e| {
+            pyo3::exceptions::PyIOError::new_err(format!(
+                "Failed to read target YAML '{}': {}",
+                path, e
+            ))
+        })?;
+
+        probe_rs::config::add_target_from_yaml(file).map_err(|e| {
+            pyo3::exceptions::PyValueError::new_err(format!(
+                "Invalid target YAML '{}': {}",
+                path, e
+            ))
+        })
+    }
+
+    /// Refuse chip names that probe-rs would resolve to one of several variants
+    /// with different memory maps.
     ///
-    /// Example:
-    ///     >>> session.attach()
-    fn attach(&self) -> PyResult<()> {
+    /// probe-rs accepts a name prefix (e.g. "STM32L476RG" for "STM32L476RGTx") and
+    /// silently picks the first matching variant. That's harmless when all
+    /// candidates share a memory map (package variants), but picks the wrong flash
+    /// / RAM layout when they don't. An exact variant name always passes.
+    fn check_chip_unambiguous(&self) -> PyResult<()> {
+        // Unknown names are reported by probe-rs itself during attach
+        let Ok(candidates) = probe_rs::config::search_chips(&self.chip) else {
+            return Ok(());
+        };
+        if candidates.len() < 2 || candidates.iter().any(|c| c.eq_ignore_ascii_case(&self.chip)) {
+            return Ok(());
+        }
+
+        let memory_maps: Vec<_> = candidates
+            .iter()
+            .filter_map(|name| probe_rs::config::get_target_by_name(name).ok())
+            .map(|target| target.memory_map)
+            .collect();
+        if memory_maps.windows(2).all(|pair| pair[0] == pair[1]) {
+            return Ok(());
+        }
+
+        Err(pyo3::exceptions::PyValueError::new_err(format!(
+            "Chip name '{}' is ambiguous: it matches variants with different memory maps.\n\
+             Use one of these fully-qualified names: {}",
+            self.chip,
+            candidates.join(", ")
+        )))
+    }
+
+    /// Pick the debug probe to use: the one matching `probe_selector`, else the first.
+    fn select_probe(&self) -> PyResult<DebugProbeInfo> {
         let lister = Lister::new();
         let probes = lister.list_all();
 
@@ -171,315 +5611,417 @@ impl ProbeRsSession {
         }
 
         // Select probe: if selector provided, filter; else take first
-        let probe_info = if let Some(ref selector) = self.probe_selector {
+        if let Some(ref selector) = self.probe_selector {
+            let usb_id = parse_usb_selector(selector);
             probes
-                .iter()
-                .find(|p| {
-                    p.serial_number
-                        .as_ref()
-                        .map_or(false, |s| s.contains(selector))
-                        || p.identifier.contains(selector)
+                .into_iter()
+                .find(|p| match usb_id {
+                    Some((vid, pid, ref serial)) => {
+                        p.vendor_id == vid
+                            && p.product_id == pid
+                            && serial.as_ref().map_or(true, |s| p.serial_number.as_deref() == Some(s))
+                    }
+                    None => {
+                        p.serial_number
+                            .as_ref()
+                            .map_or(false, |s| s.contains(selector))
+                            || p.identifier.contains(selector)
+                    }
                 })
                 .ok_or_else(|| {
                     pyo3::exceptions::PyRuntimeError::new_err(format!(
                         "No probe matching '{}' found",
                         selector
                     ))
-                })?
+                })
+        } else if matches!(
+            Family::from_chip(&self.chip),
+            Some(Family::Esp32C3 | Family::Esp32C6 | Family::Esp32S3)
+        ) {
+            // ESP32 boards often expose other USB devices (UART bridges, a second
+            // probe) next to the built-in USB-Serial-JTAG; prefer the latter
+            let index = probes
+                .iter()
+                .position(|p| (p.vendor_id, p.product_id) == ESP_USB_JTAG)
+                .unwrap_or(0);
+            Ok(probes.into_iter().nth(index).unwrap())
         } else {
-            &probes[0]
-        };
+            Ok(probes.into_iter().next().unwrap())
+        }
+    }
 
+    /// Open `probe_info` and attach to the configured chip.
+    ///
+    /// With `force_swd`, the wire protocol is explicitly set to SWD before attaching
+    /// instead of relying on the probe's default. attach() rejects that together
+    /// with a scan chain, so the JTAG setup below is never skipped by it.
+    fn open_and_attach(&self, probe_info: &DebugProbeInfo, force_swd: bool, under_reset: bool) -> PyResult<Session> {
         // Open the probe
-        let probe = probe_info
+        let mut probe = probe_info
             .open()
             .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to open probe: {}", e)))?;
 
-        // Attach to target with SWD
-        let session = probe
-            .attach(&self.chip, Permissions::default())
-            .map_err(|e| {
+        if force_swd {
+            probe.select_protocol(WireProtocol::Swd).map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to select SWD protocol: {}", e))
+            })?;
+        } else if let Some(ir_lengths) = &self.scan_chain {
+            // The chain has to be described before attach: probe-rs otherwise
+            // assumes the target is the only TAP and shifts the wrong IR width
+            probe.select_protocol(WireProtocol::Jtag).map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to select JTAG protocol: {}", e))
+            })?;
+            let chain = ir_lengths
+                .iter()
+                .map(|ir_len| ScanChainElement {
+                    name: None,
+                    ir_len: Some(*ir_len),
+                })
+                .collect();
+            probe.set_scan_chain(chain).map_err(|e| {
+                pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to configure JTAG scan chain: {}", e))
+            })?;
+            probe.select_jtag_tap(self.jtag_tap).map_err(|e| {
                 pyo3::exceptions::PyRuntimeError::new_err(format!(
-                    "Failed to attach to chip '{}': {}. Check chip name and power.",
-                    self.chip, e
+                    "Failed to select JTAG TAP {}: {}",
+                    self.jtag_tap, e
                 ))
             })?;
+        }
 
-        // Store session
-        *self.session.lock().unwrap() = Some(session);
-
-        Ok(())
+        // Default permissions are the minimal set (no full-chip erase), which is
+        // also what read-only sessions rely on
+        let attached = if under_reset {
+            probe.attach_under_reset(&self.chip, Permissions::default())
+        } else {
+            probe.attach(&self.chip, Permissions::default())
+        };
+        attached.map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!(
+                "Failed to attach to chip '{}': {}. Check chip name and power.",
+                self.chip, e
+            ))
+        })
     }
 
-    /// Start RTT on the target.
-    ///
-    /// This finds the RTT control block (a struct placed by the firmware that
-    /// describes RTT channel buffers) and initializes RTT for reading/writing channels.
+    /// Read the buffer descriptors of the attached RTT control block.
     ///
-    /// Three methods to locate the control block (in priority order):
-    /// Priority order (if both parameters provided, block_address takes precedence):
-    /// 1. If `block_address` provided: Use that exact address (fastest, elf_path ignored)
-    /// 2. If `elf_path` provided: Read _SEGGER_RTT symbol from ELF (reliable)
-    /// 3. Otherwise: Scan all RAM for the control block signature (slow, may fail)
-    ///
-    /// Args:
-    ///     elf_path: Optional path to ELF file (e.g., "build/zephyr/zephyr.elf").
-    ///         probe-rs will read the _SEGGER_RTT symbol address from the ELF.
-    ///         This is the RECOMMENDED approach - always works if firmware has RTT.
-    ///     block_address: Optional RTT control block address (e.g., 0x20001010).
-    ///         If provided, skips ELF parsing and RAM scanning (elf_path is ignored).
-    ///         Use this for maximum speed if you know the exact address.
-    ///
-    /// Returns:
-    ///     int: Number of up (target→host) channels found
+    /// The buffer counts come from target RAM, so they are checked before use: a
+    /// block zeroed by a target reset, or overwritten with garbage, declares fewer
+    /// buffers than were found at attach (or an absurd number). That raises
+    /// RttError and sets `rtt_desync` instead of indexing past the descriptors.
+    fn rtt_descriptors(&self, core: &mut Core, rtt: &mut Rtt) -> PyResult<RttDescriptors> {
+        let block = u64::from(rtt.ptr());
+        let (num_up, num_down) = (rtt.up_channels().len(), rtt.down_channels().len());
+        let max_buffers = read_regs(core, self.retry_policy, block + RTT_MAX_UP_OFFSET, 2)?;
+        let (max_up, max_down) = (max_buffers[0] as usize, max_buffers[1] as usize);
+        if !(num_up..=RTT_MAX_BUFFERS).contains(&max_up) || !(num_down..=RTT_MAX_BUFFERS).contains(&max_down) {
+            self.rtt_desync.store(true, Ordering::Relaxed);
+            return Err(RttError::new_err(format!(
+                "RTT control block at 0x{:08x} declares {} up / {} down buffers, expected at least \
+                 {} / {} (target reset?); call check_rtt_valid(resync=True) or start_rtt() again.",
+                block, max_up, max_down, num_up, num_down
+            )));
+        }
+
+        let words = read_regs(
+            core,
+            self.retry_policy,
+            block + RTT_DESCRIPTORS_OFFSET,
+            (max_up + max_down) * RTT_DESCRIPTOR_WORDS,
+        )?;
+        Ok(RttDescriptors { max_up, words })
+    }
+
+    /// Run `f` against core 0 of the attached session.
     ///
-    /// Raises:
-    ///     RuntimeError: If not attached, or RTT control block not found
+    /// Takes the session lock for the duration of the call, so `f` must not try to
+    /// lock `self.session` again.
+    fn with_core<T>(&self, f: impl FnOnce(&mut Core) -> PyResult<T>) -> PyResult<T> {
+        self.access_core(true, f)
+    }
+
+    /// Run `f` against core 0 and the started RTT instance.
     ///
-    /// Example:
-    ///     >>> # RECOMMENDED: Use ELF to find RTT symbol (works with any probe)
-    ///     >>> num_channels = session.start_rtt(elf_path="build/zephyr/zephyr.elf")
-    ///     >>> # Fallback: Auto-scan RAM (may fail with some probes/targets)
-    ///     >>> num_channels = session.start_rtt()
-    ///     >>> # Fastest: Use known address
-    ///     >>> num_channels = session.start_rtt(block_address=0x20001010)
-    #[pyo3(signature = (elf_path=None, block_address=None))]
-    fn start_rtt(&self, elf_path: Option<String>, block_address: Option<u64>) -> PyResult<usize> {
+    /// Like `with_core`, but without the lockup check: RTT is polled in tight loops
+    /// where the extra DHCSR read per call would cost throughput, and the control
+    /// block stays readable in lockup anyway. The RTT lock is taken inside the
+    /// session lock, the same order as everywhere else.
+    fn with_rtt<T>(&self, f: impl FnOnce(&mut Core, &mut Rtt) -> PyResult<T>) -> PyResult<T> {
+        self.access_core(false, |core| {
+            let mut rtt_guard = self.rtt.lock().unwrap();
+            let rtt = rtt_guard.as_mut().ok_or_else(|| {
+                pyo3::exceptions::PyRuntimeError::new_err("RTT not started. Call start_rtt() first.")
+            })?;
+            f(core, rtt)
+        })
+    }
+
+    /// Shared body of `with_core` and `with_rtt`: disconnect detection and (if
+    /// `check_lockup`) the lockup policy around one core access.
+    fn access_core<T>(&self, check_lockup: bool, f: impl FnOnce(&mut Core) -> PyResult<T>) -> PyResult<T> {
+        self.check_connected()?;
         let mut session_guard = self.session.lock().unwrap();
         let session = session_guard
             .as_mut()
-            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Not attached. Call attach() first."))?;
-
-        // Attach to core 0
-        let mut core = session.core(0).map_err(|e| {
-            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to attach to core: {}", e))
-        })?;
+            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Not attached"))?;
 
-        // Determine RTT control block address (priority: explicit > ELF symbol > RAM scan)
-        let rtt_address = if let Some(addr) = block_address {
-            // Priority 1: Explicit address provided (fastest)
-            Some(addr)
-        } else if let Some(ref elf) = elf_path {
-            // Priority 2: Read _SEGGER_RTT symbol from ELF
-            match find_rtt_symbol(elf)? {
-                Some(addr) => Some(addr),
-                None => {
-                    return Err(pyo3::exceptions::PyRuntimeError::new_err(format!(
-                        "_SEGGER_RTT symbol not found in ELF file '{}'.\n\
-                         Make sure firmware was built with RTT enabled (CONFIG_USE_SEGGER_RTT=y for Zephyr)",
-                        elf
-                    )));
+        let mut lockup_warning = false;
+        let (result, disconnected) = match session.core(0) {
+            Ok(mut core) => {
+                if check_lockup {
+                    lockup_warning = self.check_lockup(&mut core)?;
                 }
+                let result = f(&mut core);
+                // Errors from `f` are already PyErrs, so ask the probe whether it's still there
+                let disconnected = result.is_err() && core.core_halted().is_err_and(|e| is_disconnect(&e));
+                (result, disconnected)
+            }
+            Err(e) => {
+                let disconnected = is_disconnect(&e);
+                let error = format!("Failed to access core: {}", e);
+                (Err(pyo3::exceptions::PyRuntimeError::new_err(error)), disconnected)
             }
-        } else {
-            // Priority 3: Will scan RAM (may fail)
-            None
-        };
-
-        // Attach to RTT control block
-        let mut rtt = if let Some(addr) = rtt_address {
-            // Use known address (from explicit param or ELF symbol)
-            Rtt::attach_at(&mut core, addr).map_err(|e| {
-                pyo3::exceptions::PyRuntimeError::new_err(format!(
-                    "RTT control block not found at 0x{:08x}: {}.\n\
-                     The address is correct but the control block may not be initialized yet.\n\
-                     Make sure firmware has called SEGGER_RTT_Init() or rtt_init!() before connecting.",
-                    addr, e
-                ))
-            })?
-        } else {
-            // Auto-scan RAM regions (slowest, may fail with some probes)
-            Rtt::attach(&mut core).map_err(|e| {
-                pyo3::exceptions::PyRuntimeError::new_err(format!(
-                    "RTT control block not found via RAM scan: {}.\n\
-                     RECOMMENDED FIX: Use start_rtt(elf_path='build/zephyr/zephyr.elf') instead.\n\
-                     This reads the _SEGGER_RTT symbol address from your ELF file, which is\n\
-                     much more reliable than scanning (especially with ST-Link probes).",
-                    e
-                ))
-            })?
         };
 
-        let num_up = rtt.up_channels().len();
+        if disconnected {
+            return Err(self.mark_disconnected(&mut session_guard, &mut self.rtt.lock().unwrap()));
+        }
 
-        // Store RTT state
-        *self.rtt.lock().unwrap() = Some(rtt);
+        // The warning needs the GIL, which must never be taken with the session
+        // locked: callers inside allow_threads would otherwise deadlock against a
+        // thread that holds the GIL and is waiting for the session
+        drop(session_guard);
+        if lockup_warning {
+            Self::warn_lockup()?;
+        }
+        result
+    }
 
-        Ok(num_up)
+    /// Raise ProbeDisconnectedError if the probe was found unplugged since the last attach().
+    fn check_connected(&self) -> PyResult<()> {
+        if self.probe_disconnected.load(Ordering::Relaxed) {
+            return Err(ProbeDisconnectedError::new_err(
+                "Probe disconnected; call attach() to reconnect",
+            ));
+        }
+        Ok(())
     }
 
-    /// Read raw bytes from an RTT up (target→host) channel.
-    ///
-    /// Non-blocking: returns empty bytes if no data available.
-    ///
-    /// Args:
-    ///     channel: RTT up channel index (0-based)
-    ///
-    /// Returns:
-    ///     bytes: Raw data from the channel (may be empty)
+    /// Drop a session whose probe has gone away and return the error to raise.
     ///
-    /// Raises:
-    ///     RuntimeError: If RTT not started or channel doesn't exist
-    ///
-    /// Example:
-    ///     >>> data = session.rtt_read(channel=0)
-    ///     >>> if data:
-    ///     ...     print(f"Received {len(data)} bytes")
-    fn rtt_read(&self, channel: usize) -> PyResult<Py<PyBytes>> {
-        let mut session_guard = self.session.lock().unwrap();
-        let session = session_guard
-            .as_mut()
-            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Not attached"))?;
-
-        let mut rtt_guard = self.rtt.lock().unwrap();
-        let rtt = rtt_guard
-            .as_mut()
-            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("RTT not started. Call start_rtt() first."))?;
-
-        // Attach to core to perform read
-        let mut core = session.core(0).map_err(|e| {
-            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to access core: {}", e))
-        })?;
-
-        // Get the up channel (up_channels returns a mutable slice)
-        let up_channel = rtt
-            .up_channels()
-            .get_mut(channel)
-            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("Channel {} not found", channel)))?;
-
-        // Read up to 4KB at a time
-        let mut buffer = vec![0u8; 4096];
-        let count = up_channel.read(&mut core, &mut buffer).map_err(|e| {
-            pyo3::exceptions::PyRuntimeError::new_err(format!("RTT read failed: {}", e))
-        })?;
-
-        // Return as Python bytes
-        buffer.truncate(count);
-        Python::with_gil(|py| Ok(PyBytes::new(py, &buffer).into()))
+    /// Takes the already-locked session and RTT state, so callers holding those
+    /// locks don't deadlock. Until the next attach(), check_connected() fails.
+    fn mark_disconnected(&self, session: &mut Option<Session>, rtt: &mut Option<Rtt>) -> PyErr {
+        *session = None;
+        *rtt = None;
+        self.probe_disconnected.store(true, Ordering::Relaxed);
+        ProbeDisconnectedError::new_err(
+            "Probe disconnected (USB device gone); call attach() to reconnect",
+        )
     }
 
-    /// Write raw bytes to an RTT down (host→target) channel.
-    ///
-    /// Args:
-    ///     channel: RTT down channel index (0-based)
-    ///     data: Bytes to write
-    ///
-    /// Returns:
-    ///     int: Number of bytes actually written (may be less than len(data) if buffer full)
-    ///
-    /// Raises:
-    ///     RuntimeError: If RTT not started or channel doesn't exist
+    /// Apply the lockup policy before a core access.
     ///
-    /// Example:
-    ///     >>> written = session.rtt_write(channel=0, data=b"command")
-    ///     >>> print(f"Wrote {written} bytes")
-    fn rtt_write(&self, channel: usize, data: &[u8]) -> PyResult<usize> {
-        let mut session_guard = self.session.lock().unwrap();
-        let session = session_guard
-            .as_mut()
-            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Not attached"))?;
-
-        let mut rtt_guard = self.rtt.lock().unwrap();
-        let rtt = rtt_guard
-            .as_mut()
-            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("RTT not started"))?;
-
-        let mut core = session.core(0).map_err(|e| {
-            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to access core: {}", e))
-        })?;
-
-        let down_channel = rtt
-            .down_channels()
-            .get_mut(channel)
-            .ok_or_else(|| pyo3::exceptions::PyValueError::new_err(format!("Channel {} not found", channel)))?;
+    /// Returns true if the caller should emit the lockup warning (once per
+    /// lockup, with the 'warn' policy). The warning itself is left to the caller
+    /// because this runs with the session locked, where taking the GIL can
+    /// deadlock. A DHCSR read that fails is not treated as lockup; the access
+    /// itself will report the underlying problem.
+    fn check_lockup(&self, core: &mut Core) -> PyResult<bool> {
+        if self.lockup_policy == LockupPolicy::Ignore || core.architecture() != Architecture::Arm {
+            return Ok(false);
+        }
+        let locked_up = core
+            .read_word_32(cortex_m::DHCSR)
+            .is_ok_and(|dhcsr| dhcsr & cortex_m::DHCSR_S_LOCKUP != 0);
+        if !locked_up {
+            self.lockup_warned.store(false, Ordering::Relaxed);
+            return Ok(false);
+        }
 
-        let written = down_channel.write(&mut core, data).map_err(|e| {
-            pyo3::exceptions::PyRuntimeError::new_err(format!("RTT write failed: {}", e))
-        })?;
+        match self.lockup_policy {
+            LockupPolicy::Raise => Err(pyo3::exceptions::PyRuntimeError::new_err(
+                "Core is in lockup (DHCSR.S_LOCKUP set); access refused by lockup_policy='raise'",
+            )),
+            _ => Ok(!self.lockup_warned.swap(true, Ordering::Relaxed)),
+        }
+    }
 
-        Ok(written)
+    /// Emit the RuntimeWarning for a core found in lockup. Must not be called with
+    /// the session locked.
+    fn warn_lockup() -> PyResult<()> {
+        Python::with_gil(|py| {
+            PyErr::warn(
+                py,
+                &py.get_type::<pyo3::exceptions::PyRuntimeWarning>(),
+                c"Core is in lockup (DHCSR.S_LOCKUP set); memory access is still attempted, \
+                  but the core is not executing. Reset to recover.",
+                1,
+            )
+        })
     }
+}
 
-    /// Reset the target chip.
-    ///
-    /// Args:
-    ///     halt: If True, halt the core after reset (for debugging)
-    ///
-    /// Raises:
-    ///     RuntimeError: If not attached
-    ///
-    /// Example:
-    ///     >>> session.reset(halt=False)
-    #[pyo3(signature = (halt=false))]
-    fn reset(&self, halt: bool) -> PyResult<()> {
-        let mut session_guard = self.session.lock().unwrap();
-        let session = session_guard
-            .as_mut()
-            .ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Not attached"))?;
+/// Log every probe transaction to a file, for debugging attach/RTT problems.
+///
+/// probe-rs instruments its probe drivers and debug-port layer with `tracing`
+/// events; at trace level that includes each SWD/JTAG register read and write with
+/// its address and result. This installs a process-wide subscriber that writes
+/// those events to `path`. It is much more verbose than normal logging and slows
+/// transfers down, so only enable it to capture a trace for a bug report.
+///
+/// Can only be enabled once per process.
+///
+/// Args:
+///     path: File to write the log to (truncated if it exists)
+///     filter: tracing filter directive (default "probe_rs=trace"), e.g.
+///         "probe_rs::architecture::arm=trace" to limit it to the ARM debug layer
+///
+/// Raises:
+///     IOError: If the file can't be created
+///     RuntimeError: If logging was already enabled in this process
+///
+/// Example:
+///     >>> import eab_probe_rs
+///     >>> eab_probe_rs.enable_transaction_log("/tmp/probe-trace.log")
+#[pyfunction]
+#[pyo3(signature = (path, filter="probe_rs=trace"))]
+fn enable_transaction_log(path: &str, filter: &str) -> PyResult<()> {
+    let file = fs::File::create(path).map_err(|e| {
+        pyo3::exceptions::PyIOError::new_err(format!(
+            "Failed to create log file '{}': {}",
+            path, e
+        ))
+    })?;
 
-        let mut core = session.core(0).map_err(|e| {
-            pyo3::exceptions::PyRuntimeError::new_err(format!("Failed to access core: {}", e))
-        })?;
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::new(filter))
+        .with_writer(Mutex::new(file))
+        .with_ansi(false)
+        .try_init()
+        .map_err(|e| {
+            pyo3::exceptions::PyRuntimeError::new_err(format!(
+                "Failed to enable transaction log: {}",
+                e
+            ))
+        })
+}
 
-        core.reset().map_err(|e| {
-            pyo3::exceptions::PyRuntimeError::new_err(format!("Reset failed: {}", e))
-        })?;
+/// A USB scan started by `list_probes()`, shared by every call waiting for it.
+struct ProbeScan {
+    /// Set once the scan ends: the probes found, or None if the scan thread panicked.
+    result: Mutex<Option<Option<Vec<DebugProbeInfo>>>>,
+    done: Condvar,
+}
 
-        if halt {
-            core.halt(std::time::Duration::from_millis(100)).map_err(|e| {
-                pyo3::exceptions::PyRuntimeError::new_err(format!("Halt failed: {}", e))
-            })?;
-        }
+/// Scan in progress, if any. Concurrent calls and calls after a timeout wait on it
+/// instead of starting another, so a hung scan costs one thread, not one per call.
+/// Only locked long enough to look up or install a scan, never while waiting.
+static PENDING_PROBE_SCAN: Mutex<Option<Arc<ProbeScan>>> = Mutex::new(None);
 
-        Ok(())
-    }
+/// List the debug probes connected to this machine.
+///
+/// Enumeration runs on a worker thread: on hosts with many USB devices (or one
+/// that stops responding) a USB scan can take a long time or hang, and this
+/// keeps the caller responsive. probe-rs reports probes in one batch, so there
+/// are no partial results: if the scan doesn't finish within `timeout_ms`,
+/// TimeoutError is raised and the scan is left running. The next call waits for
+/// that same scan rather than starting a new one.
+///
+/// Args:
+///     timeout_ms: How long to wait for the scan (default 5000)
+///
+/// Returns:
+///     list[dict], one per probe, with keys:
+///         identifier: str probe name as reported by the driver
+///         vendor_id: int USB vendor ID
+///         product_id: int USB product ID
+///         serial_number: str | None USB serial number
+///         selector: str "VID:PID[:SERIAL]" string to pass as probe_selector
+///
+/// Raises:
+///     TimeoutError: If the scan doesn't finish within timeout_ms
+///     RuntimeError: If the scan thread died
+///
+/// Example:
+///     >>> for probe in list_probes(timeout_ms=2000):
+///     ...     print(probe["identifier"], probe["selector"])
+#[pyfunction]
+#[pyo3(signature = (timeout_ms=5000))]
+fn list_probes(py: Python<'_>, timeout_ms: u64) -> PyResult<Bound<'_, PyList>> {
+    let scan = PENDING_PROBE_SCAN
+        .lock()
+        .unwrap()
+        .get_or_insert_with(|| {
+            let scan = Arc::new(ProbeScan {
+                result: Mutex::new(None),
+                done: Condvar::new(),
+            });
+            let worker = Arc::clone(&scan);
+            std::thread::spawn(move || {
+                let probes = std::panic::catch_unwind(|| Lister::new().list_all()).ok();
+                *worker.result.lock().unwrap() = Some(probes);
+                worker.done.notify_all();
+            });
+            scan
+        })
+        .clone();
 
-    /// Detach from the target and close the probe connection.
-    ///
-    /// Always call this when done to release the probe for other tools.
-    ///
-    /// Example:
-    ///     >>> session.detach()
-    fn detach(&self) -> PyResult<()> {
-        *self.session.lock().unwrap() = None;
-        *self.rtt.lock().unwrap() = None;
-        Ok(())
-    }
+    let outcome = py.allow_threads(|| {
+        let result = scan.result.lock().unwrap();
+        let (result, _) = scan
+            .done
+            .wait_timeout_while(result, Duration::from_millis(timeout_ms), |result| result.is_none())
+            .unwrap();
+        result.clone()
+    });
+    let Some(outcome) = outcome else {
+        return Err(pyo3::exceptions::PyTimeoutError::new_err(format!(
+            "Probe scan did not finish within {} ms (still running; a later call picks it up)",
+            timeout_ms
+        )));
+    };
 
-    /// Get the chip name this session is configured for.
-    ///
-    /// Returns:
-    ///     str: Chip name
-    #[getter]
-    fn chip(&self) -> String {
-        self.chip.clone()
+    // Finished, so the next call starts a fresh scan (unless another call already has)
+    let mut pending = PENDING_PROBE_SCAN.lock().unwrap();
+    if pending.as_ref().is_some_and(|pending| Arc::ptr_eq(pending, &scan)) {
+        *pending = None;
     }
+    drop(pending);
+    let probes = outcome.ok_or_else(|| pyo3::exceptions::PyRuntimeError::new_err("Probe scan thread failed"))?;
 
-    /// Check if currently attached to a target.
-    ///
-    /// Returns:
-    ///     bool: True if attached
-    #[getter]
-    fn is_attached(&self) -> bool {
-        self.session.lock().unwrap().is_some()
-    }
+    let list = PyList::empty(py);
+    for probe in probes {
+        let mut selector = format!("{:04x}:{:04x}", probe.vendor_id, probe.product_id);
+        if let Some(serial) = &probe.serial_number {
+            selector = format!("{}:{}", selector, serial);
+        }
 
-    /// Check if RTT is active.
-    ///
-    /// Returns:
-    ///     bool: True if RTT started
-    #[getter]
-    fn is_rtt_active(&self) -> bool {
-        self.rtt.lock().unwrap().is_some()
+        let entry = PyDict::new(py);
+        entry.set_item("identifier", &probe.identifier)?;
+        entry.set_item("vendor_id", probe.vendor_id)?;
+        entry.set_item("product_id", probe.product_id)?;
+        entry.set_item("serial_number", &probe.serial_number)?;
+        entry.set_item("selector", selector)?;
+        list.append(entry)?;
     }
+    Ok(list)
 }
 
 /// Python module initialization.
 ///
-/// This registers the `ProbeRsSession` class so Python can import it:
+/// This registers the `ProbeRsSession` class and module-level functions so Python
+/// can import them:
 ///     >>> from eab_probe_rs import ProbeRsSession
 #[pymodule]
 fn eab_probe_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<ProbeRsSession>()?;
+    m.add_class::<MemoryBuffer>()?;
+    m.add_class::<MemoryWatch>()?;
+    m.add("RttError", m.py().get_type::<RttError>())?;
+    m.add("ProbeDisconnectedError", m.py().get_type::<ProbeDisconnectedError>())?;
+    m.add_function(wrap_pyfunction!(enable_transaction_log, m)?)?;
+    m.add_function(wrap_pyfunction!(list_probes, m)?)?;
     Ok(())
 }
@@ -2,6 +2,7 @@
 
 from __future__ import annotations
 
+import contextlib
 import json
 import os
 import shutil
@@ -11,11 +12,45 @@ from typing import Optional
 
 from eab.cli.helpers import _print
 
+# Field separator for --log-format; ASCII unit separator never appears in log text.
+_SEP = '\x1f'
+_JSONL_LOG_FORMAT = _SEP.join(['{t}', '{L}', '{m}', '{F}', '{l}', '{s}'])
+
+
+def _parse_records(lines: list[str]) -> list[dict]:
+    """Turn defmt-print lines in _JSONL_LOG_FORMAT into record dicts.
+
+    Lines without separators continue the previous record's message (multi-line
+    log messages).
+    """
+    records: list[dict] = []
+    for line in lines:
+        fields = line.split(_SEP, 5)
+        if len(fields) != 6:
+            if records:
+                records[-1]['message'] += '\n' + line
+            else:
+                records.append({'timestamp': None, 'level': None, 'module': None,
+                                'file': None, 'line': None, 'message': line})
+            continue
+        timestamp, level, module, file, lineno, message = fields
+        records.append({
+            'timestamp': timestamp or None,
+            'level': level.strip().lower() or None,
+            'module': module or None,
+            'file': file or None,
+            'line': int(lineno) if lineno.isdigit() else None,
+            'message': message,
+        })
+    return records
+
 
 def cmd_defmt_decode(
     elf: str,
     input_file: Optional[str] = None,
     base_dir: Optional[str] = None,
+    output_format: str = 'text',
+    output_file: Optional[str] = None,
     json_mode: bool = False,
 ) -> int:
     defmt_print = shutil.which('defmt-print')
@@ -31,6 +66,8 @@ def cmd_defmt_decode(
         return 1
 
     cmd = [defmt_print, '-e', elf]
+    if output_format == 'jsonl':
+        cmd += ['--log-format', _JSONL_LOG_FORMAT]
 
     if input_file:
         source_path = input_file
@@ -58,7 +95,18 @@ def cmd_defmt_decode(
             return 1
 
         lines = result.stdout.strip().splitlines()
-        if json_mode:
+        if output_format == 'jsonl':
+            records = _parse_records(lines)
+            with open(output_file, 'w') if output_file else contextlib.nullcontext(sys.stdout) as out:
+                for record in records:
+                    out.write(json.dumps(record) + '\n')
+            if output_file:
+                _print({'output': output_file, 'count': len(records)}, json_mode=json_mode)
+        elif output_file:
+            with open(output_file, 'w') as out:
+                out.writelines(line + '\n' for line in lines)
+            _print({'output': output_file, 'count': len(lines)}, json_mode=json_mode)
+        elif json_mode:
             decoded = []
             for line in lines:
                 decoded.append({'message': line})
@@ -568,6 +568,8 @@ def main(argv: Optional[list[str]] = None) -> int:
                 elf=args.elf,
                 input_file=args.input_file,
                 base_dir=base_dir if getattr(args, "from_rtt", False) else None,
+                output_format=args.output_format,
+                output_file=args.output,
                 json_mode=args.json,
             )
 
@@ -686,6 +686,14 @@ def _build_parser() -> argparse.ArgumentParser:
     p_defmt_decode.add_argument(
         "--from-rtt", action="store_true", help="Read from device RTT log (uses base_dir/rtt.log)"
     )
+    p_defmt_decode.add_argument(
+        "--format",
+        dest="output_format",
+        default="text",
+        choices=["text", "jsonl"],
+        help="Output format: formatted text, or one JSON object per record (default: text)",
+    )
+    p_defmt_decode.add_argument("--output", "-o", default=None, help="Write records to this file instead of stdout")
 
     # --- DWT hardware watchpoints ---
     p_dwt = sub.add_parser("dwt", help="DWT hardware watchpoints (non-halting stream)")
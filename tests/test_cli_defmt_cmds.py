@@ -0,0 +1,204 @@
+"""CLI tests for eabctl defmt decode."""
+
+from __future__ import annotations
+
+import json
+import subprocess
+from unittest.mock import MagicMock, patch
+
+import pytest
+
+from eab.cli.defmt_cmd import _SEP, _parse_records, cmd_defmt_decode
+
+
+def _record_line(timestamp="", level="", module="", file="", lineno="", message=""):
+    """Build one defmt-print output line in the --format jsonl log format."""
+    return _SEP.join([timestamp, level, module, file, lineno, message])
+
+
+# =============================================================================
+# _parse_records
+# =============================================================================
+
+class TestParseRecords:
+
+    def test_fields_split_on_separator(self):
+        lines = [_record_line("1.234", "INFO", "app::net", "src/net.rs", "42", "link up")]
+        assert _parse_records(lines) == [{
+            'timestamp': '1.234',
+            'level': 'info',
+            'module': 'app::net',
+            'file': 'src/net.rs',
+            'line': 42,
+            'message': 'link up',
+        }]
+
+    def test_separator_in_message_is_kept(self):
+        """Only the first five separators split fields; the message keeps the rest."""
+        lines = [_record_line("0", "WARN", "m", "f.rs", "1", f"a{_SEP}b")]
+        assert _parse_records(lines)[0]['message'] == f"a{_SEP}b"
+
+    @pytest.mark.parametrize("raw, expected", [
+        ("ERROR", "error"),
+        (" Debug ", "debug"),
+        ("", None),
+    ])
+    def test_level_lowercased(self, raw, expected):
+        lines = [_record_line("0", raw, "m", "f.rs", "1", "msg")]
+        assert _parse_records(lines)[0]['level'] == expected
+
+    @pytest.mark.parametrize("lineno", ["", "?", "12a", "-3"])
+    def test_non_numeric_line_number_is_none(self, lineno):
+        lines = [_record_line("0", "INFO", "m", "f.rs", lineno, "msg")]
+        assert _parse_records(lines)[0]['line'] is None
+
+    def test_empty_fields_become_none(self):
+        record = _parse_records([_record_line(message="bare")])[0]
+        assert record['timestamp'] is None
+        assert record['module'] is None
+        assert record['file'] is None
+        assert record['message'] == "bare"
+
+    def test_continuation_lines_join_previous_message(self):
+        lines = [
+            _record_line("0", "INFO", "m", "f.rs", "1", "first"),
+            "second",
+            "third",
+            _record_line("1", "INFO", "m", "f.rs", "2", "next"),
+        ]
+        records = _parse_records(lines)
+        assert len(records) == 2
+        assert records[0]['message'] == "first\nsecond\nthird"
+        assert records[1]['message'] == "next"
+
+    def test_leading_continuation_starts_bare_record(self):
+        records = _parse_records(["no fields here"])
+        assert records == [{'timestamp': None, 'level': None, 'module': None,
+                            'file': None, 'line': None, 'message': "no fields here"}]
+
+    def test_empty_input(self):
+        assert _parse_records([]) == []
+
+
+# =============================================================================
+# Parser tests
+# =============================================================================
+
+class TestParserDefmtDecode:
+
+    def test_defaults(self):
+        from eab.cli.parser import _build_parser
+        p = _build_parser()
+        args = p.parse_args(["defmt", "decode", "--elf", "/tmp/fw.elf"])
+        assert args.cmd == "defmt"
+        assert args.defmt_action == "decode"
+        assert args.output_format == "text"
+        assert args.output is None
+        assert args.input_file is None
+
+    def test_format_and_output(self):
+        from eab.cli.parser import _build_parser
+        p = _build_parser()
+        args = p.parse_args([
+            "defmt", "decode",
+            "--elf", "/tmp/fw.elf",
+            "--format", "jsonl",
+            "-o", "/tmp/out.jsonl",
+        ])
+        assert args.output_format == "jsonl"
+        assert args.output == "/tmp/out.jsonl"
+
+    def test_unknown_format_rejected(self):
+        from eab.cli.parser import _build_parser
+        p = _build_parser()
+        with pytest.raises(SystemExit):
+            p.parse_args(["defmt", "decode", "--elf", "/tmp/fw.elf", "--format", "csv"])
+
+
+# =============================================================================
+# Dispatch tests
+# =============================================================================
+
+class TestDefmtDispatch:
+
+    def test_format_and_output_forwarded(self, tmp_path):
+        from eab.control import main
+
+        mock_cmd = MagicMock(return_value=0)
+        with patch("eab.cli.defmt_cmd.cmd_defmt_decode", mock_cmd):
+            result = main([
+                "defmt", "decode",
+                "--elf", str(tmp_path / "fw.elf"),
+                "--input", str(tmp_path / "rtt.bin"),
+                "--format", "jsonl",
+                "--output", str(tmp_path / "out.jsonl"),
+            ])
+        assert result == 0
+        kwargs = mock_cmd.call_args.kwargs
+        assert kwargs["elf"] == str(tmp_path / "fw.elf")
+        assert kwargs["input_file"] == str(tmp_path / "rtt.bin")
+        assert kwargs["output_format"] == "jsonl"
+        assert kwargs["output_file"] == str(tmp_path / "out.jsonl")
+        assert kwargs["base_dir"] is None
+        assert kwargs["json_mode"] is False
+
+    def test_failure_propagates(self, tmp_path):
+        from eab.control import main
+
+        mock_cmd = MagicMock(return_value=1)
+        with patch("eab.cli.defmt_cmd.cmd_defmt_decode", mock_cmd):
+            result = main(["defmt", "decode", "--elf", str(tmp_path / "fw.elf")])
+        assert result == 1
+
+
+# =============================================================================
+# cmd_defmt_decode
+# =============================================================================
+
+class TestCmdDefmtDecode:
+
+    @pytest.fixture
+    def inputs(self, tmp_path):
+        elf = tmp_path / "fw.elf"
+        elf.write_bytes(b"\x7fELF")
+        raw = tmp_path / "rtt.bin"
+        raw.write_bytes(b"\x01\x02")
+        return elf, raw
+
+    def _run(self, inputs, stdout, **kwargs):
+        elf, raw = inputs
+        completed = subprocess.CompletedProcess(args=[], returncode=0, stdout=stdout, stderr="")
+        with patch("eab.cli.defmt_cmd.shutil.which", return_value="/usr/bin/defmt-print"), \
+                patch("eab.cli.defmt_cmd.subprocess.run", return_value=completed) as run:
+            result = cmd_defmt_decode(elf=str(elf), input_file=str(raw), **kwargs)
+        return result, run
+
+    def test_jsonl_passes_log_format(self, inputs, capsys):
+        line = _record_line("0", "INFO", "m", "f.rs", "7", "hello")
+        result, run = self._run(inputs, line + "\n", output_format="jsonl")
+        assert result == 0
+        cmd = run.call_args.args[0]
+        assert "--log-format" in cmd
+        record = json.loads(capsys.readouterr().out)
+        assert record['message'] == "hello"
+        assert record['line'] == 7
+
+    def test_jsonl_to_output_file(self, inputs, tmp_path, capsys):
+        out = tmp_path / "out.jsonl"
+        stdout = "\n".join([
+            _record_line("0", "INFO", "m", "f.rs", "1", "one"),
+            _record_line("1", "WARN", "m", "f.rs", "2", "two"),
+        ])
+        result, _ = self._run(inputs, stdout, output_format="jsonl", output_file=str(out), json_mode=True)
+        assert result == 0
+        records = [json.loads(line) for line in out.read_text().splitlines()]
+        assert [r['level'] for r in records] == ["info", "warn"]
+        summary = json.loads(capsys.readouterr().out)
+        assert summary == {'output': str(out), 'count': 2}
+
+    def test_text_to_output_file(self, inputs, tmp_path):
+        out = tmp_path / "out.txt"
+        result, run = self._run(inputs, "a\nb\n", output_file=str(out))
+        assert result == 0
+        assert "--log-format" not in run.call_args.args[0]
+        assert out.read_text() == "a\nb\n"